@@ -295,6 +295,64 @@ pub trait UiAutomator2AppCompatible: AppiumCapability {
     fn enforce_app_install(&mut self, value: bool) {
         self.set_bool("appium:enforceAppInstall", value);
     }
+
+    /// Port used by UiAutomator2 server on the host machine.
+    ///
+    /// Must be unique for every session if you run many Android sessions in parallel (on one Appium server).
+    /// By default, a free port from 8200..8299 range is selected.
+    fn system_port(&mut self, port: u16) {
+        self.set_number("appium:systemPort", Number::from(port));
+    }
+
+    /// Port of MJPEG screenshot server on the host machine.
+    ///
+    /// Must be unique for every session if you run many Android sessions in parallel and use MJPEG screen streaming.
+    fn mjpeg_server_port(&mut self, port: u16) {
+        self.set_number("appium:mjpegServerPort", Number::from(port));
+    }
+
+    /// Id of the display to automate, on devices with many displays (e.g. foldables or Android Auto).
+    ///
+    /// See [crate::commands::android::HasAndroidDeviceDetails::active_displays] for available ids.
+    fn display_id(&mut self, id: u32) {
+        self.set_number("appium:displayId", Number::from(id));
+    }
+
+    /// Skips installation of UiAutomator2 server (and its checks).
+    ///
+    /// Speeds up session startup if the server is already installed on the device (e.g. by a previous session).
+    fn skip_server_installation(&mut self, value: bool) {
+        self.set_bool("appium:skipServerInstallation", value);
+    }
+
+    /// Skips device initialization (e.g. checking permissions, setting up locale or the settings app).
+    ///
+    /// Speeds up session startup, but use it only if the device has been initialized already (e.g. by a previous session).
+    fn skip_device_initialization(&mut self, value: bool) {
+        self.set_bool("appium:skipDeviceInitialization", value);
+    }
+}
+
+/// Capabilities specific to Espresso driver (Android).
+pub trait EspressoAppCompatible: AppiumCapability {
+    /// Build config of Espresso server: a path to JSON file or the JSON itself (e.g. with `toolsVersions` and `additionalAndroidTestDependencies`).
+    ///
+    /// See <https://github.com/appium/appium-espresso-driver#espresso-build-config>.
+    fn espresso_build_config(&mut self, config: &str) {
+        self.set_str("appium:espressoBuildConfig", config);
+    }
+
+    /// Always rebuild Espresso server (instead of using a cached one).
+    ///
+    /// Useful if you changed build config. `false` by default.
+    fn force_espresso_rebuild(&mut self, value: bool) {
+        self.set_bool("appium:forceEspressoRebuild", value);
+    }
+
+    /// Print Gradle log of Espresso server build into Appium log. `false` by default.
+    fn show_gradle_log(&mut self, value: bool) {
+        self.set_bool("appium:showGradleLog", value);
+    }
 }
 
 /// Capabilities for Settings API (<https://appium.io/docs/en/2.1/guides/settings/>).
@@ -28,7 +28,7 @@
 use std::ops::{Deref, DerefMut};
 use fantoccini::wd::Capabilities;
 use serde_json::Value;
-use crate::capabilities::{AppCapable, AppiumCapability, AppiumSettingsCapable, UdidCapable, UiAutomator2AppCompatible};
+use crate::capabilities::{AppCapable, AppiumCapability, AppiumSettingsCapable, EspressoAppCompatible, UdidCapable, UiAutomator2AppCompatible};
 use crate::capabilities::automation::{ANDROID_UIAUTOMATOR2, ESPRESSO};
 
 /// Android capabilities
@@ -99,3 +99,74 @@ impl AppiumSettingsCapable for AndroidCapabilities {}
 
 impl UiAutomator2AppCompatible for AndroidCapabilities {}
 
+impl EspressoAppCompatible for AndroidCapabilities {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use crate::capabilities::android::AndroidCapabilities;
+    use crate::capabilities::{EspressoAppCompatible, UiAutomator2AppCompatible};
+    use crate::ClientBuilder;
+    use crate::mock::MockTransport;
+
+    async fn requested_capabilities(capabilities: AndroidCapabilities) -> Value {
+        let transport = MockTransport::new();
+        ClientBuilder::with_connector(transport.clone(), capabilities)
+            .connect("http://localhost:4723/")
+            .await
+            .expect("mock session should be created");
+
+        let new_session = transport.requests().into_iter().next().unwrap();
+        assert_eq!(new_session.path, "/session");
+        new_session.body.unwrap()["capabilities"]["alwaysMatch"].clone()
+    }
+
+    #[tokio::test]
+    async fn ports_of_uiautomator2_are_sent_in_new_session() {
+        let mut capabilities = AndroidCapabilities::new_uiautomator();
+        capabilities.system_port(8201);
+        capabilities.mjpeg_server_port(7811);
+
+        let requested = requested_capabilities(capabilities).await;
+
+        assert_eq!(requested["appium:systemPort"], json!(8201));
+        assert_eq!(requested["appium:mjpegServerPort"], json!(7811));
+    }
+
+    #[tokio::test]
+    async fn espresso_build_options_are_sent_in_new_session() {
+        let mut capabilities = AndroidCapabilities::new_espresso();
+        capabilities.espresso_build_config(r#"{"toolsVersions": {"compileSdk": 34}}"#);
+        capabilities.force_espresso_rebuild(true);
+        capabilities.show_gradle_log(true);
+
+        let requested = requested_capabilities(capabilities).await;
+
+        assert_eq!(requested["appium:automationName"], json!("Espresso"));
+        assert_eq!(requested["appium:espressoBuildConfig"], json!(r#"{"toolsVersions": {"compileSdk": 34}}"#));
+        assert_eq!(requested["appium:forceEspressoRebuild"], json!(true));
+        assert_eq!(requested["appium:showGradleLog"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn startup_skips_are_sent_in_new_session() {
+        let mut capabilities = AndroidCapabilities::new_uiautomator();
+        capabilities.skip_server_installation(true);
+        capabilities.skip_device_initialization(true);
+
+        let requested = requested_capabilities(capabilities).await;
+
+        assert_eq!(requested["appium:skipServerInstallation"], json!(true));
+        assert_eq!(requested["appium:skipDeviceInitialization"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn display_id_is_sent_in_new_session() {
+        let mut capabilities = AndroidCapabilities::new_uiautomator();
+        capabilities.display_id(2);
+
+        let requested = requested_capabilities(capabilities).await;
+
+        assert_eq!(requested["appium:displayId"], json!(2));
+    }
+}
@@ -72,12 +72,46 @@ pub mod recording;
 pub mod clipboard;
 pub mod battery;
 pub mod ios;
+pub mod multitouch;
+pub mod window;
+pub mod input;
+pub mod gestures;
+pub mod status;
+pub mod logs;
+pub mod text;
+pub mod alerts;
+pub mod source;
 
+use fantoccini::error::CmdError;
 use fantoccini::wd::WebDriverCompatibleCommand;
+use serde::de::DeserializeOwned;
 use http::Method;
 use serde_json::Value;
 use crate::find::By;
 
+/// Parses the result of a command, so the error says which command returned unexpected result.
+///
+/// Use it instead of `serde_json::from_value` when implementing commands.
+///
+/// ```
+/// use fantoccini::error::CmdError;
+/// use serde_json::json;
+/// use appium_client::commands::parse_response;
+///
+/// let density: u32 = parse_response("appium/device/display_density", json!(420)).unwrap();
+/// assert_eq!(density, 420);
+///
+/// let error = parse_response::<u32>("appium/device/display_density", json!("dense")).unwrap_err();
+/// assert!(matches!(error, CmdError::Json(_)));
+/// assert!(error.to_string().contains("appium/device/display_density"));
+/// ```
+pub fn parse_response<T>(cmd_name: &str, value: Value) -> Result<T, CmdError>
+    where T: DeserializeOwned
+{
+    T::deserialize(&value)
+        .map_err(|e| CmdError::Json(serde::de::Error::custom(format!("Unexpected result of {cmd_name} ({e}): {value}"))))
+}
+
 /// Basic Appium commands
 ///
 /// Use Custom if you want to implement anything non-standard.
@@ -89,6 +123,43 @@ pub enum AppiumCommand {
     FindElements(By),
     FindElementsWithContext(By, String),
     Custom(Method, String, Option<Value>),
+    /// Same as [AppiumCommand::Custom], but the path is relative to server url, not to the session (e.g. `status`).
+    CustomGlobal(Method, String, Option<Value>),
+}
+
+impl AppiumCommand {
+    /// Path of the command relative to the session url (e.g. `appium/device/lock`).
+    ///
+    /// For [AppiumCommand::CustomGlobal], the path is relative to server url.
+    ///
+    /// Does not need an url nor a session, so it's handy if you want to check what your custom command does.
+    ///
+    /// ```
+    /// use http::Method;
+    /// use appium_client::commands::AppiumCommand;
+    /// use appium_client::find::By;
+    ///
+    /// let lock = AppiumCommand::Custom(Method::POST, "appium/device/lock".to_string(), None);
+    /// assert_eq!(lock.relative_path(), "appium/device/lock");
+    ///
+    /// let find = AppiumCommand::FindElementWithContext(By::id("button"), "1234".to_string());
+    /// assert_eq!(find.relative_path(), "element/1234/element");
+    /// ```
+    pub fn relative_path(&self) -> String {
+        match self {
+            AppiumCommand::FindElement(..) =>
+                "element".to_string(),
+            AppiumCommand::FindElements(..) =>
+                "elements".to_string(),
+            AppiumCommand::FindElementWithContext(.., context) =>
+                format!("element/{context}/element"),
+            AppiumCommand::FindElementsWithContext(.., context) =>
+                format!("element/{context}/elements"),
+            AppiumCommand::Custom(_, command, ..)
+            | AppiumCommand::CustomGlobal(_, command, ..) =>
+                command.clone(),
+        }
+    }
 }
 
 impl WebDriverCompatibleCommand for AppiumCommand {
@@ -97,23 +168,12 @@ impl WebDriverCompatibleCommand for AppiumCommand {
         base_url: &url::Url,
         session_id: Option<&str>,
     ) -> Result<url::Url, url::ParseError> {
-        let base = { base_url.join(&format!("session/{}/", session_id.as_ref().unwrap()))? };
-        match self {
-            AppiumCommand::FindElement(..) =>
-                base.join("element"),
-            AppiumCommand::FindElements(..) =>
-                base.join("elements"),
-            AppiumCommand::FindElementWithContext(.., context) =>
-                base.join("element")
-                    .and_then(|url| url.join(context))
-                    .and_then(|url| url.join("element")),
-            AppiumCommand::FindElementsWithContext(.., context) =>
-                base.join("element")
-                    .and_then(|url| url.join(context))
-                    .and_then(|url| url.join("elements")),
-            AppiumCommand::Custom(_, command, ..) =>
-                base.join(command),
+        if let AppiumCommand::CustomGlobal(..) = self {
+            return base_url.join(&self.relative_path());
         }
+
+        let base = { base_url.join(&format!("session/{}/", session_id.as_ref().unwrap()))? };
+        base.join(&self.relative_path())
     }
 
     fn method_and_body(&self, _request_url: &url::Url) -> (Method, Option<String>) {
@@ -128,7 +188,8 @@ impl WebDriverCompatibleCommand for AppiumCommand {
                 (method, body)
             },
 
-            AppiumCommand::Custom(method, .., value) => {
+            AppiumCommand::Custom(method, .., value)
+            | AppiumCommand::CustomGlobal(method, .., value) => {
                 let body = value.clone()
                     .map(|v| v.to_string());
 
@@ -0,0 +1,111 @@
+//! Native alerts (system dialogs)
+use std::future::Future;
+use std::time::Duration;
+use async_trait::async_trait;
+use fantoccini::error::CmdError;
+use log::{debug, warn};
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+
+/// How often the watcher of [AcceptsAlerts::with_auto_accept_alerts] checks for an alert.
+const ALERT_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Accept alerts automatically
+#[async_trait]
+pub trait AcceptsAlerts: AppiumClientTrait {
+    /// Runs `body` while accepting every native alert that appears (e.g. permission dialogs).
+    ///
+    /// A background task checks for an alert every 500ms and accepts it.
+    /// The task is stopped when `body` completes (or panics, or the returned future is dropped).
+    /// It's like `autoAcceptAlerts` capability (or a UiAutomator watcher), but only for a part of your test.
+    ///
+    /// ```no_run
+    /// # use appium_client::AndroidClient;
+    /// # use appium_client::commands::alerts::AcceptsAlerts;
+    /// # use appium_client::find::{AppiumFind, By};
+    /// # async fn grant(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// client.with_auto_accept_alerts(|| async {
+    ///     client.find_by(By::id("take_photo")).await?.click().await
+    /// }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn with_auto_accept_alerts<F, Fut, T>(&self, body: F) -> Result<T, CmdError>
+        where F: FnOnce() -> Fut + Send,
+              Fut: Future<Output = Result<T, CmdError>> + Send,
+              T: Send
+    {
+        let client = self.deref().clone();
+        let task = tokio::spawn(async move {
+            let mut interval = interval(ALERT_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                match client.accept_alert().await {
+                    Ok(_) => debug!("Alert accepted automatically"),
+                    Err(CmdError::NoSuchAlert(_)) => {},
+                    Err(e) => warn!("Error while accepting alert: {e}"),
+                }
+            }
+        });
+
+        // the task is aborted when the guard is dropped
+        let _watcher = AbortOnDrop(task);
+
+        body().await
+    }
+}
+
+#[async_trait]
+impl AcceptsAlerts for AndroidClient {}
+
+#[async_trait]
+impl AcceptsAlerts for IOSClient {}
+
+/// Task that is aborted when dropped (also when the future that owns it is cancelled or panics).
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use serde_json::json;
+    use crate::commands::alerts::AcceptsAlerts;
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    #[tokio::test]
+    async fn alerts_are_accepted_while_body_runs() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.with_auto_accept_alerts(|| async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(())
+        }).await.unwrap();
+
+        assert!(!transport.requests_to("/alert/accept").is_empty());
+    }
+
+    #[tokio::test]
+    async fn watcher_stops_when_cancelled() {
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+
+        let body = client.with_auto_accept_alerts(|| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        });
+        assert!(tokio::time::timeout(Duration::from_millis(700), body).await.is_err());
+
+        let requests = transport.requests().len();
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        assert_eq!(transport.requests().len(), requests);
+    }
+}
@@ -1,14 +1,26 @@
 //! Android-specific features
+//!
+//! Commands that run shell commands on the device (via `mobile: shell`, e.g. [StartsActivity::launchable_activity]
+//! or [TracesPerformance::with_trace]) need Appium server to be run with `--allow-insecure=adb_shell`.
+//!
+//! Methods prefixed with `mobile_` do the same as their counterparts, but with `mobile:` commands
+//! instead of the legacy Appium endpoints. Newer drivers may support only the `mobile:` variants.
 use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::str::FromStr;
 use async_trait::async_trait;
 use fantoccini::elements::Element;
 use fantoccini::error::CmdError;
 use http::Method;
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 use serde_repr::Serialize_repr;
 use serde_json::{json, Value};
 use crate::{AndroidClient, AppiumClientTrait};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
+use crate::commands::files::PullsFiles;
+use crate::find::{AppiumFind, By};
 
 pub struct AndroidActivity {
     pub app_package: String,
@@ -51,7 +63,7 @@ pub trait StartsActivity: AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/current_activity", value)
     }
 
     async fn current_package(&self) -> Result<String, CmdError> {
@@ -61,7 +73,45 @@ pub trait StartsActivity: AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/current_package", value)
+    }
+
+    /// [StartsActivity::current_activity] with `mobile: getCurrentActivity`.
+    async fn mobile_current_activity(&self) -> Result<String, CmdError> {
+        let value = self.execute("mobile: getCurrentActivity", vec![]).await?;
+        parse_response("mobile: getCurrentActivity", value)
+    }
+
+    /// [StartsActivity::current_package] with `mobile: getCurrentPackage`.
+    async fn mobile_current_package(&self) -> Result<String, CmdError> {
+        let value = self.execute("mobile: getCurrentPackage", vec![]).await?;
+        parse_response("mobile: getCurrentPackage", value)
+    }
+
+    /// Resolves the launchable (main) activity of an installed package.
+    ///
+    /// Useful if you don't know the activity of the app, but you need it to set `appActivity` or to start it.
+    /// Returns activity name as declared in manifest (e.g. `.MainActivity` or `com.example.MainActivity`).
+    /// Runs `cmd package resolve-activity` via `mobile: shell`.
+    async fn launchable_activity(&self, package: &str) -> Result<String, CmdError> {
+        let value = self.execute("mobile: shell", vec![json!({
+            "command": "cmd",
+            "args": ["package", "resolve-activity", "--brief", package]
+        })]).await?;
+
+        let output: String = parse_response("mobile: shell", value)?;
+
+        // the last line of output is the component name, e.g. com.example/.MainActivity
+        output.lines()
+            .map(|line| line.trim())
+            .rev()
+            .find(|line| !line.is_empty())
+            .and_then(|component| component.split_once('/'))
+            .map(|(_, activity)| activity.to_string())
+            .ok_or_else(|| CmdError::InvalidArgument(
+                "package".to_string(),
+                format!("{package} has no launchable activity.")
+            ))
     }
 }
 
@@ -78,7 +128,21 @@ pub trait HasAndroidDeviceDetails :AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/display_density", value)
+    }
+
+    /// Lists displays of the device (e.g. the inner and the outer display of a foldable).
+    ///
+    /// Uses `mobile: getDisplays`, so it works only if the driver supports it.
+    async fn active_displays(&self) -> Result<Vec<DisplayInfo>, CmdError> {
+        let value = self.execute("mobile: getDisplays", vec![]).await?;
+        parse_response("mobile: getDisplays", value)
+    }
+
+    /// [HasAndroidDeviceDetails::display_density] with `mobile: getDisplayDensity`.
+    async fn mobile_display_density(&self) -> Result<u64, CmdError> {
+        let value = self.execute("mobile: getDisplayDensity", vec![]).await?;
+        parse_response("mobile: getDisplayDensity", value)
     }
 
     async fn system_bars(&self) -> Result<HashMap<String, HashMap<String, Value>>, CmdError> {
@@ -88,13 +152,108 @@ pub trait HasAndroidDeviceDetails :AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/system_bars", value)
+    }
+
+    /// Same as [HasAndroidDeviceDetails::system_bars], but parsed into [SystemBars].
+    async fn system_bars_typed(&self) -> Result<SystemBars, CmdError> {
+        let value = self.issue_cmd(AppiumCommand::Custom(
+            Method::GET,
+            "appium/device/system_bars".to_string(),
+            None
+        )).await?;
+
+        parse_response("appium/device/system_bars", value)
+    }
+
+    /// Primary IPv4 address of the device (the first non-loopback one), e.g. to connect to a server running on the device.
+    /// Runs `ip addr show` via `mobile: shell`.
+    async fn device_ip(&self) -> Result<String, CmdError> {
+        let value = self.execute("mobile: shell", vec![json!({
+            "command": "ip",
+            "args": ["-4", "-o", "addr", "show"]
+        })]).await?;
+        let output: String = parse_response("mobile: shell", value)?;
+
+        parse_primary_ipv4(&output)
+            .ok_or_else(|| CmdError::InvalidArgument(
+                "ip".to_string(),
+                format!("No IPv4 address found in: {output}")
+            ))
     }
 }
 
+/// Finds the first non-loopback IPv4 address in the output of `ip -4 -o addr show`.
+pub(crate) fn parse_primary_ipv4(output: &str) -> Option<String> {
+    output.lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            words.find(|word| *word == "inet")?;
+            let address = words.next()?;
+            Some(address.split('/').next().unwrap_or(address).to_string())
+        })
+        .find(|address| !address.starts_with("127."))
+}
+
 #[async_trait]
 impl HasAndroidDeviceDetails for AndroidClient {}
 
+/// Status bar and navigation bar of Android device (see [HasAndroidDeviceDetails::system_bars_typed])
+///
+/// ```
+/// use appium_client::commands::android::SystemBars;
+///
+/// let bars: SystemBars = serde_json::from_str(r#"{
+///     "statusBar": {"visible": true, "x": 0, "y": 0, "width": 1080, "height": 63},
+///     "navigationBar": {"visible": false, "x": 0, "y": 2274, "width": 1080, "height": 126}
+/// }"#).unwrap();
+///
+/// assert!(bars.status_bar.visible);
+/// assert_eq!(bars.status_bar.height, 63);
+/// assert!(!bars.navigation_bar.visible);
+/// assert_eq!(bars.navigation_bar.y, 2274);
+/// ```
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemBars {
+    pub status_bar: SystemBar,
+    pub navigation_bar: SystemBar,
+}
+
+/// Visibility and bounds (in pixels) of a system bar
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct SystemBar {
+    pub visible: bool,
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+/// Display of an Android device (see [HasAndroidDeviceDetails::active_displays])
+///
+/// ```
+/// use appium_client::commands::android::DisplayInfo;
+///
+/// let displays: Vec<DisplayInfo> = serde_json::from_str(r#"[
+///     {"id": 0, "name": "Built-in Screen", "isDefault": true},
+///     {"id": 2, "name": "Cover Screen"}
+/// ]"#).unwrap();
+///
+/// assert_eq!(displays[1].id, 2);
+/// assert!(displays[0].is_default);
+/// assert!(!displays[1].is_default);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    /// Display id, to be used with [crate::capabilities::UiAutomator2AppCompatible::display_id].
+    pub id: u32,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
 /// Device traits that Appium is able to read
 #[async_trait]
 pub trait HasSupportedPerformanceDataType : AppiumClientTrait {
@@ -105,7 +264,18 @@ pub trait HasSupportedPerformanceDataType : AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/performanceData/types", value)
+    }
+
+    /// Same as [HasSupportedPerformanceDataType::supported_performance_data_type], but returns typed values.
+    ///
+    /// Names not known to this lib are returned as [PerformanceDataType::Other].
+    async fn supported_performance_data_types_typed(&self) -> Result<Vec<PerformanceDataType>, CmdError> {
+        let types = self.supported_performance_data_type().await?;
+
+        Ok(types.iter()
+            .map(|name| PerformanceDataType::from(name.as_str()))
+            .collect())
     }
 
     async fn performance_data(&self, package: &str, data_type: &str, read_timeout: u32) -> Result<Vec<Vec<Value>>, CmdError> {
@@ -119,13 +289,63 @@ pub trait HasSupportedPerformanceDataType : AppiumClientTrait {
             }))
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/performanceData/types", value)
     }
 }
 
 #[async_trait]
 impl HasSupportedPerformanceDataType for AndroidClient {}
 
+/// Performance data type (that Appium is able to read)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PerformanceDataType {
+    CpuInfo,
+    MemoryInfo,
+    BatteryInfo,
+    NetworkInfo,
+    /// Data type not known to this lib.
+    Other(String),
+}
+
+impl PerformanceDataType {
+    /// Name of data type, as used by Appium.
+    pub fn as_str(&self) -> &str {
+        match self {
+            PerformanceDataType::CpuInfo => "cpuinfo",
+            PerformanceDataType::MemoryInfo => "memoryinfo",
+            PerformanceDataType::BatteryInfo => "batteryinfo",
+            PerformanceDataType::NetworkInfo => "networkinfo",
+            PerformanceDataType::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for PerformanceDataType {
+    fn from(value: &str) -> Self {
+        match value {
+            "cpuinfo" => PerformanceDataType::CpuInfo,
+            "memoryinfo" => PerformanceDataType::MemoryInfo,
+            "batteryinfo" => PerformanceDataType::BatteryInfo,
+            "networkinfo" => PerformanceDataType::NetworkInfo,
+            other => PerformanceDataType::Other(other.to_string()),
+        }
+    }
+}
+
+impl FromStr for PerformanceDataType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PerformanceDataType::from(s))
+    }
+}
+
+impl Display for PerformanceDataType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum GsmCallAction {
@@ -292,7 +512,7 @@ pub trait ExecutesCDP : AppiumClientTrait {
             }))
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("goog/cdp/execute", value)
     }
 }
 
@@ -315,7 +535,550 @@ pub trait CanReplaceValue: AppiumClientTrait {
 
         Ok(())
     }
+
+    /// [CanReplaceValue::replace_value] with `mobile: replaceElementValue`.
+    async fn mobile_replace_value(&self, element: &Element, value: &str) -> Result<(), CmdError> {
+        self.execute("mobile: replaceElementValue", vec![json!({
+            "elementId": element.element_id().to_string(),
+            "text": value
+        })]).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CanReplaceValue for AndroidClient {}
+
+/// Clearing app data (like `pm clear`)
+#[async_trait]
+pub trait ClearsAppData: AppiumClientTrait {
+    /// Clears data of given app (package) without reinstalling it.
+    ///
+    /// The app is stopped as a result. Uses `mobile: clearApp`.
+    async fn clear_app_data(&self, package: &str) -> Result<(), CmdError> {
+        self.execute("mobile: clearApp", vec![json!({
+            "appId": package
+        })]).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ClearsAppData for AndroidClient {}
+
+/// List apps installed on the device
+#[async_trait]
+pub trait ListsPackages: AppiumClientTrait {
+    /// Names of all installed packages (e.g. `com.android.chrome`), sorted.
+    /// Runs `pm list packages` via `mobile: shell`.
+    async fn installed_packages(&self) -> Result<Vec<String>, CmdError> {
+        let value = self.execute("mobile: shell", vec![json!({
+            "command": "pm",
+            "args": ["list", "packages"]
+        })]).await?;
+        let output: String = parse_response("mobile: shell", value)?;
+
+        Ok(parse_package_list(&output))
+    }
+}
+
+#[async_trait]
+impl ListsPackages for AndroidClient {}
+
+/// Reads package names from the output of `pm list packages` (sorted).
+pub(crate) fn parse_package_list(output: &str) -> Vec<String> {
+    let mut packages: Vec<String> = output.lines()
+        .filter_map(|line| line.trim().strip_prefix("package:"))
+        .map(|package| package.trim().to_string())
+        .filter(|package| !package.is_empty())
+        .collect();
+
+    packages.sort();
+    packages
+}
+
+/// Scroll to elements using UiScrollable (UiAutomator2 only)
+#[async_trait]
+pub trait ScrollsToElement: AppiumClientTrait {
+    /// Scrolls the first scrollable container on screen until target is visible, then returns the target.
+    async fn scroll_to(&self, target: By) -> Result<Element, CmdError> {
+        let query = format!(
+            "new UiScrollable(new UiSelector().scrollable(true)).scrollIntoView({})",
+            ui_selector(&target)?
+        );
+
+        self.find_by(By::uiautomator(&query)).await
+    }
+
+    /// Scrolls the given container until target is visible, then returns the target.
+    ///
+    /// Useful on screens with nested scrollables, where the first scrollable is not the one you need.
+    /// Both `container` and `target` must be one of: [By::Id] (resource-id), [By::AccessibilityId] (content-desc),
+    /// [By::ClassName] or [By::UiAutomator] (a raw `new UiSelector()...` expression).
+    async fn scroll_in(&self, container: By, target: By) -> Result<Element, CmdError> {
+        let query = format!(
+            "new UiScrollable({}).scrollIntoView({})",
+            ui_selector(&container)?,
+            ui_selector(&target)?
+        );
+
+        self.find_by(By::uiautomator(&query)).await
+    }
+}
+
+#[async_trait]
+impl ScrollsToElement for AndroidClient {}
+
+/// Translates a locator into a UiSelector expression.
+fn ui_selector(by: &By) -> Result<String, CmdError> {
+    let escape = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+
+    match by {
+        By::Id(id) => Ok(format!("new UiSelector().resourceId(\"{}\")", escape(id))),
+        By::AccessibilityId(id) => Ok(format!("new UiSelector().description(\"{}\")", escape(id))),
+        By::ClassName(class_name) => Ok(format!("new UiSelector().className(\"{}\")", escape(class_name))),
+        By::UiAutomator(selector) => Ok(selector.clone()),
+        _ => Err(CmdError::InvalidArgument(
+            "by".to_string(),
+            format!("{by:?} cannot be used as UiSelector.")
+        ))
+    }
+}
+
+/// Open and collapse the status bar (notifications and quick settings)
+///
+/// Everything except [ControlsStatusBar::open_notifications] runs `cmd statusbar` via `mobile: shell`,
+/// so Appium server needs to be run with `--allow-insecure=adb_shell`.
+#[async_trait]
+pub trait ControlsStatusBar: AppiumClientTrait {
+    /// Opens the notification shade. Uses `mobile: openNotifications`.
+    async fn open_notifications(&self) -> Result<(), CmdError> {
+        self.execute("mobile: openNotifications", vec![]).await?;
+        Ok(())
+    }
+
+    /// Opens (fully expands) the quick settings panel.
+    async fn open_quick_settings(&self) -> Result<(), CmdError> {
+        self.execute("mobile: shell", vec![status_bar_cmd(&["expand-settings"])]).await?;
+        Ok(())
+    }
+
+    /// Collapses notifications or quick settings, if they're open.
+    async fn collapse_status_bar(&self) -> Result<(), CmdError> {
+        self.execute("mobile: shell", vec![status_bar_cmd(&["collapse"])]).await?;
+        Ok(())
+    }
+
+    /// Toggles a quick settings tile, identified by its component name (e.g. `com.android.systemui/.qs.tiles.WifiTile`).
+    ///
+    /// Only supported on Android 9+ (`cmd statusbar click-tile`), and only for tiles that are added to quick settings.
+    async fn toggle_tile(&self, tile: &str) -> Result<(), CmdError> {
+        if tile.trim().is_empty() {
+            return Err(CmdError::InvalidArgument(
+                "tile".to_string(),
+                "Tile component name cannot be empty.".to_string()
+            ));
+        }
+
+        self.execute("mobile: shell", vec![status_bar_cmd(&["click-tile", tile.trim()])]).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ControlsStatusBar for AndroidClient {}
+
+/// Arguments of `mobile: shell` that runs `cmd statusbar <args>`.
+fn status_bar_cmd(args: &[&str]) -> Value {
+    let mut shell_args = vec!["statusbar"];
+    shell_args.extend_from_slice(args);
+
+    json!({
+        "command": "cmd",
+        "args": shell_args
+    })
+}
+
+/// Capture performance traces (systrace format)
+#[async_trait]
+pub trait TracesPerformance: PullsFiles {
+    /// Traces given categories (e.g. `gfx`, `view`, `am`) while running `body`.
+    /// Returns the result of `body` and the trace (in systrace text format, open it with Perfetto UI).
+    ///
+    /// UiAutomator2 has no tracing command, so this runs `atrace` via `mobile: shell`.
+    /// If the device has no `atrace`, [CmdError::InvalidArgument] is returned before `body` is run.
+    ///
+    /// ```no_run
+    ///# use appium_client::AndroidClient;
+    ///# use appium_client::commands::android::TracesPerformance;
+    ///# use appium_client::find::{AppiumFind, By};
+    ///# async fn trace(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// let (result, trace) = client.with_trace(&["gfx", "view"], || async {
+    ///     client.find_by(By::id("open_list")).await?.click().await
+    /// }).await?;
+    ///
+    /// result?;
+    /// std::fs::write("list.trace", trace)?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    async fn with_trace<F, Fut, T>(&self, categories: &[&str], body: F) -> Result<(T, Vec<u8>), CmdError>
+        where F: FnOnce() -> Fut + Send,
+              Fut: Future<Output = T> + Send,
+              T: Send
+    {
+        if categories.is_empty() || categories.iter().any(|category| category.trim().is_empty() || category.contains(char::is_whitespace)) {
+            return Err(CmdError::InvalidArgument(
+                "categories".to_string(),
+                format!("Expected a non-empty list of trace categories, got {categories:?}")
+            ));
+        }
+
+        let mut args = vec!["--async_start", "-b", TRACE_BUFFER_KB];
+        args.extend_from_slice(categories);
+
+        let value = self.execute("mobile: shell", vec![json!({
+            "command": "atrace",
+            "args": args,
+            "includeStderr": true
+        })]).await?;
+        check_atrace_output(value)?;
+
+        let result = body().await;
+
+        let value = self.execute("mobile: shell", vec![json!({
+            "command": "atrace",
+            "args": ["--async_stop", "-o", TRACE_FILE],
+            "includeStderr": true
+        })]).await?;
+        check_atrace_output(value)?;
+
+        let trace = self.pull_file(TRACE_FILE).await?;
+        Ok((result, trace))
+    }
 }
 
 #[async_trait]
-impl CanReplaceValue for AndroidClient {}
\ No newline at end of file
+impl TracesPerformance for AndroidClient {}
+
+/// Size of trace buffer (in KB), big enough for a few seconds of a few categories.
+const TRACE_BUFFER_KB: &str = "16384";
+
+/// Where the trace is saved on the device.
+const TRACE_FILE: &str = "/data/local/tmp/appium_client.trace";
+
+/// Returns an error if `mobile: shell` output (with stderr) says that `atrace` failed.
+fn check_atrace_output(value: Value) -> Result<(), CmdError> {
+    let output: HashMap<String, String> = parse_response("mobile: shell", value)?;
+    let stderr = output.get("stderr").map(|stderr| stderr.trim()).unwrap_or("");
+
+    if stderr.contains("not found") {
+        return Err(CmdError::InvalidArgument(
+            "categories".to_string(),
+            "Tracing is not supported by this device (there's no atrace).".to_string()
+        ));
+    }
+
+    if stderr.to_lowercase().contains("error") {
+        return Err(CmdError::InvalidArgument(
+            "categories".to_string(),
+            format!("atrace failed: {stderr}")
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fantoccini::elements::{Element, ElementRef};
+    use fantoccini::error::CmdError;
+    use serde_json::json;
+    use crate::commands::android::{CanReplaceValue, ClearsAppData, ControlsStatusBar, DisplayInfo, HasAndroidDeviceDetails, HasSupportedPerformanceDataType, parse_package_list, parse_primary_ipv4, PerformanceDataType, ScrollsToElement, StartsActivity, TracesPerformance};
+    use crate::find::By;
+    use crate::mock::{android_client, MockResponse, MockTransport, RecordedRequest};
+
+    /// Last request, which should be an `execute` of a `mobile:` command.
+    fn last_script(transport: &MockTransport) -> RecordedRequest {
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/execute/sync");
+        request
+    }
+
+    #[test]
+    fn primary_ipv4_skips_loopback() {
+        let output = "1: lo    inet 127.0.0.1/8 scope host lo\n\
+                      30: wlan0    inet 10.0.2.16/24 brd 10.0.2.255 scope global wlan0";
+
+        assert_eq!(parse_primary_ipv4(output), Some("10.0.2.16".to_string()));
+    }
+
+    #[test]
+    fn primary_ipv4_is_the_first_one() {
+        let output = "30: wlan0    inet 10.0.2.16/24 brd 10.0.2.255 scope global wlan0\n\
+                      31: eth0    inet 192.168.1.5/24 brd 192.168.1.255 scope global eth0";
+
+        assert_eq!(parse_primary_ipv4(output), Some("10.0.2.16".to_string()));
+    }
+
+    #[test]
+    fn no_primary_ipv4_without_network() {
+        assert_eq!(parse_primary_ipv4("1: lo    inet 127.0.0.1/8 scope host lo"), None);
+        assert_eq!(parse_primary_ipv4(""), None);
+    }
+
+    #[test]
+    fn package_list_is_sorted() {
+        let output = "package:com.android.settings\npackage:com.android.chrome\npackage:com.example.app\n";
+
+        assert_eq!(parse_package_list(output), vec!["com.android.chrome", "com.android.settings", "com.example.app"]);
+    }
+
+    #[test]
+    fn package_list_skips_line_endings_and_blank_lines() {
+        let output = "package:com.android.settings\r\n\npackage: \r\n  package:com.example.app  \n";
+
+        assert_eq!(parse_package_list(output), vec!["com.android.settings", "com.example.app"]);
+    }
+
+    #[test]
+    fn package_list_of_no_packages_is_empty() {
+        assert!(parse_package_list("").is_empty());
+        assert!(parse_package_list("Error: unknown option").is_empty());
+    }
+
+    #[tokio::test]
+    async fn launchable_activity_is_read_from_the_last_line() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("priority=0 preferredOrder=0\ncom.example/.MainActivity\n")));
+        let client = android_client(&transport).await;
+
+        let activity = client.launchable_activity("com.example").await.unwrap();
+
+        assert_eq!(activity, ".MainActivity");
+        assert_eq!(last_script(&transport).body.unwrap(), json!({
+            "script": "mobile: shell",
+            "args": [{ "command": "cmd", "args": ["package", "resolve-activity", "--brief", "com.example"] }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn package_without_launchable_activity_is_invalid() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("No activity found\n")));
+        let client = android_client(&transport).await;
+
+        let error = client.launchable_activity("com.example.service").await.unwrap_err();
+
+        assert!(error.to_string().contains("com.example.service has no launchable activity"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn scroll_in_container_uses_ui_scrollable() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::element("item-1"));
+        let client = android_client(&transport).await;
+
+        client.scroll_in(By::id("com.example:id/list"), By::accessibility_id("Item \"42\"")).await.unwrap();
+
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/element");
+        assert_eq!(request.body.unwrap(), json!({
+            "using": "-android uiautomator",
+            "value": "new UiScrollable(new UiSelector().resourceId(\"com.example:id/list\")).scrollIntoView(new UiSelector().description(\"Item \\\"42\\\"\"))"
+        }));
+    }
+
+    #[tokio::test]
+    async fn scroll_to_refuses_locators_without_ui_selector() {
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+        let requests = transport.requests().len();
+
+        let result = client.scroll_to(By::xpath("//*[@text='42']")).await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert_eq!(transport.requests().len(), requests);
+    }
+
+    #[tokio::test]
+    async fn performance_data_types_are_typed() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(["cpuinfo", "memoryinfo", "gfxinfo"])));
+        let client = android_client(&transport).await;
+
+        let types = client.supported_performance_data_types_typed().await.unwrap();
+
+        assert_eq!(types, vec![
+            PerformanceDataType::CpuInfo,
+            PerformanceDataType::MemoryInfo,
+            PerformanceDataType::Other("gfxinfo".to_string()),
+        ]);
+        assert_eq!(transport.requests().pop().unwrap().path, "/session/mock-session/appium/performanceData/types");
+    }
+
+    #[test]
+    fn performance_data_type_names_round_trip() {
+        for name in ["cpuinfo", "memoryinfo", "batteryinfo", "networkinfo", "gfxinfo"] {
+            assert_eq!(name.parse::<PerformanceDataType>().unwrap().to_string(), name);
+        }
+    }
+
+    #[tokio::test]
+    async fn current_activity_and_package_are_read_with_mobile_commands() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(".MainActivity")));
+        transport.respond(MockResponse::value(json!("com.example")));
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.mobile_current_activity().await.unwrap(), ".MainActivity");
+        assert_eq!(last_script(&transport).body.unwrap(), json!({ "script": "mobile: getCurrentActivity", "args": [] }));
+
+        assert_eq!(client.mobile_current_package().await.unwrap(), "com.example");
+        assert_eq!(last_script(&transport).body.unwrap(), json!({ "script": "mobile: getCurrentPackage", "args": [] }));
+    }
+
+    #[tokio::test]
+    async fn app_data_is_cleared_by_package() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(true)));
+        let client = android_client(&transport).await;
+
+        client.clear_app_data("com.example").await.unwrap();
+
+        assert_eq!(last_script(&transport).body.unwrap(), json!({
+            "script": "mobile: clearApp",
+            "args": [{ "appId": "com.example" }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn quick_settings_tile_is_clicked_with_statusbar_cmd() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("")));
+        let client = android_client(&transport).await;
+
+        client.toggle_tile(" com.android.systemui/.qs.tiles.WifiTile ").await.unwrap();
+
+        assert_eq!(last_script(&transport).body.unwrap(), json!({
+            "script": "mobile: shell",
+            "args": [{ "command": "cmd", "args": ["statusbar", "click-tile", "com.android.systemui/.qs.tiles.WifiTile"] }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn empty_tile_is_refused() {
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+        let requests = transport.requests().len();
+
+        assert!(matches!(client.toggle_tile(" ").await, Err(CmdError::InvalidArgument(..))));
+        assert_eq!(transport.requests().len(), requests);
+    }
+
+    #[tokio::test]
+    async fn notifications_are_opened_with_mobile_command() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.open_notifications().await.unwrap();
+
+        assert_eq!(last_script(&transport).body.unwrap(), json!({ "script": "mobile: openNotifications", "args": [] }));
+    }
+
+    #[tokio::test]
+    async fn display_density_is_read_with_mobile_command() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(420)));
+        transport.respond(MockResponse::value(json!("dense")));
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.mobile_display_density().await.unwrap(), 420);
+        assert_eq!(last_script(&transport).body.unwrap(), json!({ "script": "mobile: getDisplayDensity", "args": [] }));
+
+        let error = client.mobile_display_density().await.unwrap_err();
+        assert!(matches!(error, CmdError::Json(_)));
+        assert!(error.to_string().contains("mobile: getDisplayDensity"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn trace_is_started_before_body_and_stopped_after() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "stdout": "", "stderr": "" })));
+        transport.respond(MockResponse::value(json!("<hierarchy/>")));
+        transport.respond(MockResponse::value(json!({ "stdout": "", "stderr": "" })));
+        transport.respond(MockResponse::value(json!("dHJhY2U=")));
+        let client = android_client(&transport).await;
+
+        let (source, trace) = client.with_trace(&["gfx", "view"], || client.source()).await.unwrap();
+
+        assert_eq!(source.unwrap(), "<hierarchy/>");
+        assert_eq!(trace, b"trace");
+        let requests: Vec<_> = transport.requests().into_iter().skip(1).collect();
+        let paths: Vec<_> = requests.iter().map(|request| request.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            "/session/mock-session/execute/sync",
+            "/session/mock-session/source",
+            "/session/mock-session/execute/sync",
+            "/session/mock-session/appium/device/pull_file",
+        ]);
+        assert_eq!(requests[0].body.as_ref().unwrap()["args"][0]["args"], json!(["--async_start", "-b", "16384", "gfx", "view"]));
+        assert_eq!(requests[2].body.as_ref().unwrap()["args"][0]["args"][0], "--async_stop");
+    }
+
+    #[tokio::test]
+    async fn body_is_not_run_without_atrace() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "stdout": "", "stderr": "/system/bin/sh: atrace: not found" })));
+        let client = android_client(&transport).await;
+
+        let result = client.with_trace(&["gfx"], || async { panic!("body should not run") }).await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+    }
+
+    #[tokio::test]
+    async fn value_is_replaced_with_mobile_command() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+        let field = Element::from_element_id((*client).clone(), ElementRef::from("field-1".to_string()));
+
+        client.mobile_replace_value(&field, "hello").await.unwrap();
+
+        assert_eq!(last_script(&transport).body.unwrap(), json!({
+            "script": "mobile: replaceElementValue",
+            "args": [{ "elementId": "field-1", "text": "hello" }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn active_displays_are_parsed() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!([
+            { "id": 0, "name": "Built-in Screen", "isDefault": true },
+            { "id": 2, "name": "Cover Screen", "isDefault": false }
+        ])));
+        let client = android_client(&transport).await;
+
+        let displays = client.active_displays().await.unwrap();
+
+        assert_eq!(displays, vec![
+            DisplayInfo { id: 0, name: Some("Built-in Screen".to_string()), is_default: true },
+            DisplayInfo { id: 2, name: Some("Cover Screen".to_string()), is_default: false },
+        ]);
+        assert_eq!(last_script(&transport).body.unwrap(), json!({ "script": "mobile: getDisplays", "args": [] }));
+    }
+
+    #[tokio::test]
+    async fn device_without_ipv4_is_an_invalid_argument() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("1: lo    inet 127.0.0.1/8 scope host lo\n")));
+        let client = android_client(&transport).await;
+
+        assert!(matches!(client.device_ip().await, Err(CmdError::InvalidArgument(name, _)) if name == "ip"));
+    }
+}
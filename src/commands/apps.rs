@@ -4,9 +4,12 @@ use async_trait::async_trait;
 use fantoccini::error::CmdError;
 use http::Method;
 use serde_json::json;
-use serde::{Serialize, Deserialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, interval};
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
+use crate::error::TerminateAppsError;
 
 /// Inspect or install other apps
 #[async_trait]
@@ -22,6 +25,61 @@ pub trait InteractsWithApps: AppiumClientTrait {
         Ok(())
     }
 
+    /// Installs an app, but does not rely on the install command finishing within the command timeout.
+    ///
+    /// The install command is fired in the background, then [InteractsWithApps::is_app_installed] is checked every `poll`
+    /// until the app (`bundle_id`) is installed or `timeout` is exceeded.
+    /// So a short command timeout won't abort a legitimately slow install of a large app.
+    ///
+    /// If the install command fails, its error is returned at once. Only if it's cut off (e.g. the connection is lost),
+    /// the install may still be in progress, so the app is polled until `timeout` (then that error is returned, or [CmdError::WaitTimeout] if there was none).
+    async fn install_app_async(&self, path: &str, bundle_id: &str, poll: Duration, timeout: Duration) -> Result<(), CmdError> {
+        if poll.is_zero() {
+            return Err(CmdError::InvalidArgument(
+                "poll".to_string(),
+                "Poll interval must be greater than zero.".to_string()
+            ));
+        }
+
+        let client = self.deref().clone();
+        let command = AppiumCommand::Custom(
+            Method::POST,
+            "appium/device/install_app".to_string(),
+            Some(json!({
+                "appPath": path
+            })),
+        );
+        let mut install = Some(AbortOnDrop(tokio::spawn(async move {
+            client.issue_cmd(command).await
+        })));
+        let mut install_error = None;
+
+        let start = Instant::now();
+        let mut interval = interval(poll);
+
+        loop {
+            interval.tick().await;
+
+            if install.as_ref().is_some_and(|install| install.0.is_finished()) {
+                let mut finished = install.take().unwrap();
+                match (&mut finished.0).await {
+                    Ok(Ok(_)) => return Ok(()),
+                    Ok(Err(e @ (CmdError::Lost(_) | CmdError::Failed(_)))) => install_error = Some(e),
+                    Ok(Err(e)) => return Err(e),
+                    Err(e) => return Err(CmdError::Lost(e.into())),
+                }
+            }
+
+            if self.is_app_installed(bundle_id).await? {
+                return Ok(());
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(install_error.unwrap_or(CmdError::WaitTimeout));
+            }
+        }
+    }
+
     async fn is_app_installed(&self, bundle_id: &str) -> Result<bool, CmdError> {
         let value = self.issue_cmd(AppiumCommand::Custom(
             Method::POST,
@@ -31,7 +89,7 @@ pub trait InteractsWithApps: AppiumClientTrait {
             })),
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/app_installed", value)
     }
 
     async fn run_app_in_background(&self, duration: Duration) -> Result<(), CmdError> {
@@ -46,6 +104,18 @@ pub trait InteractsWithApps: AppiumClientTrait {
         Ok(())
     }
 
+    /// Puts the app in background, for some time or until it's activated again.
+    ///
+    /// Unlike [InteractsWithApps::run_app_in_background], it can leave the app in background
+    /// (with [BackgroundDuration::Indefinite]). Uses `mobile: backgroundApp`.
+    async fn background_app(&self, duration: BackgroundDuration) -> Result<(), CmdError> {
+        self.execute("mobile: backgroundApp", vec![json!({
+            "seconds": duration.seconds()
+        })]).await?;
+
+        Ok(())
+    }
+
     async fn remove_app(&self, bundle_id: &str) -> Result<(), CmdError> {
         self.issue_cmd(AppiumCommand::Custom(
             Method::POST,
@@ -79,7 +149,12 @@ pub trait InteractsWithApps: AppiumClientTrait {
             })),
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/app_state", value)
+    }
+
+    /// Checks if given app is in foreground, e.g. to assert that the app under test didn't crash (to home screen).
+    async fn is_app_in_foreground(&self, bundle_id: &str) -> Result<bool, CmdError> {
+        Ok(self.app_state(bundle_id).await? == AppState::RunningInForeground)
     }
 
     async fn terminate_app(&self, bundle_id: &str) -> Result<(), CmdError> {
@@ -93,17 +168,107 @@ pub trait InteractsWithApps: AppiumClientTrait {
 
         Ok(())
     }
+
+    /// Terminates all given apps, e.g. to leave the device clean after a test.
+    ///
+    /// Unlike [InteractsWithApps::terminate_app], it does not stop on the first error.
+    /// Apps that are not installed are skipped, other failures are collected into [TerminateAppsError].
+    async fn terminate_apps(&self, bundle_ids: &[&str]) -> Result<(), TerminateAppsError> {
+        let mut failures = vec![];
+
+        for bundle_id in bundle_ids {
+            let Err(error) = self.terminate_app(bundle_id).await else {
+                continue;
+            };
+
+            // there is no common error for a missing app, so it's better to just ask about it
+            if let Ok(AppState::NotInstalled) = self.app_state(bundle_id).await {
+                continue;
+            }
+
+            failures.push((bundle_id.to_string(), error));
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(TerminateAppsError { failures })
+        }
+    }
+}
+
+/// Background task that is aborted when dropped (e.g. with the future of [InteractsWithApps::install_app_async]).
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// How long to keep the app in background (see [InteractsWithApps::background_app])
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BackgroundDuration {
+    /// Returns the app to foreground after given number of seconds.
+    Seconds(u64),
+    /// Leaves the app in background.
+    Indefinite,
+}
+
+impl BackgroundDuration {
+    /// Value of `seconds` argument, as Appium expects it (negative means "leave in background").
+    ///
+    /// ```
+    /// use appium_client::commands::apps::BackgroundDuration;
+    ///
+    /// assert_eq!(BackgroundDuration::Seconds(5).seconds(), 5);
+    /// assert_eq!(BackgroundDuration::Indefinite.seconds(), -1);
+    /// ```
+    pub fn seconds(&self) -> i64 {
+        match self {
+            BackgroundDuration::Seconds(seconds) => i64::try_from(*seconds).unwrap_or(i64::MAX),
+            BackgroundDuration::Indefinite => -1,
+        }
+    }
+}
+
+/// State of an app on the device
+///
+/// Appium returns it as a number:
+/// * `0` - [AppState::NotInstalled]
+/// * `1` - [AppState::NotRunning]
+/// * `2` - [AppState::RunningInBackgroundSuspended]
+/// * `3` - [AppState::RunningInBackground]
+/// * `4` - [AppState::RunningInForeground]
+///
+/// ```
+/// use appium_client::commands::apps::AppState;
+///
+/// let state: AppState = serde_json::from_str("4").unwrap();
+/// assert_eq!(state, AppState::RunningInForeground);
+/// assert_eq!(AppState::from_u8(0), Some(AppState::NotInstalled));
+/// assert_eq!(AppState::from_u8(5), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum AppState {
+    NotInstalled = 0,
+    NotRunning = 1,
+    RunningInBackgroundSuspended = 2,
+    RunningInBackground = 3,
+    RunningInForeground = 4,
 }
 
-bitflags::bitflags! {
-    #[repr(transparent)]
-    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
-    pub struct AppState: i8 {
-        const NOT_INSTALLED = 0;
-        const NOT_RUNNING = 1;
-        const RUNNING_IN_BACKGROUND_SUSPENDED = 2;
-        const RUNNING_IN_BACKGROUND = 3;
-        const RUNNING_IN_FOREGROUND = 4;
+impl AppState {
+    pub fn from_u8(value: u8) -> Option<AppState> {
+        match value {
+            0 => Some(AppState::NotInstalled),
+            1 => Some(AppState::NotRunning),
+            2 => Some(AppState::RunningInBackgroundSuspended),
+            3 => Some(AppState::RunningInBackground),
+            4 => Some(AppState::RunningInForeground),
+            _ => None,
+        }
     }
 }
 
@@ -111,4 +276,133 @@ bitflags::bitflags! {
 impl InteractsWithApps for AndroidClient {}
 
 #[async_trait]
-impl InteractsWithApps for IOSClient {}
\ No newline at end of file
+impl InteractsWithApps for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use fantoccini::error::CmdError;
+    use http::StatusCode;
+    use serde_json::json;
+    use tokio::time::Instant;
+    use crate::commands::apps::{AppState, InteractsWithApps};
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    fn installed_polls(transport: &MockTransport) -> usize {
+        transport.requests_to("/appium/device/app_installed").len()
+    }
+
+    #[tokio::test]
+    async fn install_completes_after_several_polls() {
+        let transport = MockTransport::new();
+        // the install command itself is cut off (like by a short command timeout)
+        transport.respond_to("appium/device/install_app", MockResponse::Disconnect);
+        transport.respond(MockResponse::value(json!(false)));
+        transport.respond(MockResponse::value(json!(false)));
+        transport.respond(MockResponse::value(json!(true)));
+        let client = android_client(&transport).await;
+
+        client.install_app_async("/apps/large.apk", "com.example.large", Duration::from_millis(10), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(installed_polls(&transport), 3);
+        let install = transport.requests_to("/appium/device/install_app").remove(0);
+        assert_eq!(install.body.unwrap(), json!({
+            "appPath": "/apps/large.apk"
+        }));
+    }
+
+    #[tokio::test]
+    async fn failed_install_returns_without_waiting_for_timeout() {
+        let transport = MockTransport::new();
+        transport.respond_to("appium/device/install_app", MockResponse::error(StatusCode::INTERNAL_SERVER_ERROR, "unknown error", "INSTALL_FAILED_INSUFFICIENT_STORAGE"));
+        for _ in 0..100 {
+            transport.respond(MockResponse::value(json!(false)));
+        }
+        let client = android_client(&transport).await;
+        let start = Instant::now();
+
+        let error = client.install_app_async("/apps/large.apk", "com.example.large", Duration::from_millis(10), Duration::from_secs(60))
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("INSTALL_FAILED_INSUFFICIENT_STORAGE"), "{error}");
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(installed_polls(&transport) < 10);
+    }
+
+    #[tokio::test]
+    async fn cut_off_install_is_polled_until_timeout() {
+        let transport = MockTransport::new();
+        transport.respond_to("appium/device/install_app", MockResponse::Disconnect);
+        for _ in 0..100 {
+            transport.respond(MockResponse::value(json!(false)));
+        }
+        let client = android_client(&transport).await;
+
+        let result = client.install_app_async("/apps/large.apk", "com.example.large", Duration::from_millis(10), Duration::from_millis(50)).await;
+
+        assert!(matches!(result, Err(CmdError::Lost(_) | CmdError::Failed(_))), "{result:?}");
+        assert!(installed_polls(&transport) > 1);
+    }
+
+    #[tokio::test]
+    async fn zero_poll_interval_is_refused() {
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+        let requests = transport.requests().len();
+
+        let result = client.install_app_async("/apps/large.apk", "com.example.large", Duration::ZERO, Duration::from_secs(1)).await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(name, _)) if name == "poll"));
+        assert_eq!(transport.requests().len(), requests);
+    }
+
+    #[tokio::test]
+    async fn terminate_apps_skips_missing_apps_and_collects_failures() {
+        let transport = MockTransport::new();
+        // com.example.first
+        transport.respond(MockResponse::value(json!(true)));
+        // com.example.missing
+        transport.respond(MockResponse::error(StatusCode::INTERNAL_SERVER_ERROR, "unknown error", "The application com.example.missing is not installed"));
+        transport.respond(MockResponse::value(json!(0)));
+        // com.example.stuck
+        transport.respond(MockResponse::error(StatusCode::INTERNAL_SERVER_ERROR, "unknown error", "Cannot terminate com.example.stuck"));
+        transport.respond(MockResponse::value(json!(4)));
+        let client = android_client(&transport).await;
+
+        let error = client.terminate_apps(&["com.example.first", "com.example.missing", "com.example.stuck"])
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.failures.len(), 1);
+        assert_eq!(error.failures[0].0, "com.example.stuck");
+        assert!(error.failures[0].1.to_string().contains("Cannot terminate"), "{}", error.failures[0].1);
+
+        let terminated: Vec<_> = transport.requests_to("/appium/device/terminate_app").into_iter()
+            .map(|request| request.body.unwrap()["bundleId"].clone())
+            .collect();
+        assert_eq!(terminated, vec![json!("com.example.first"), json!("com.example.missing"), json!("com.example.stuck")]);
+    }
+
+    #[test]
+    fn app_states_are_deserialized_from_numbers() {
+        let states = [
+            (0, AppState::NotInstalled),
+            (1, AppState::NotRunning),
+            (2, AppState::RunningInBackgroundSuspended),
+            (3, AppState::RunningInBackground),
+            (4, AppState::RunningInForeground),
+        ];
+
+        for (number, state) in states {
+            assert_eq!(serde_json::from_value::<AppState>(json!(number)).unwrap(), state);
+        }
+    }
+
+    #[test]
+    fn unknown_app_state_is_not_deserialized() {
+        assert!(serde_json::from_value::<AppState>(json!(5)).is_err());
+    }
+}
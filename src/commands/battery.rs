@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use fantoccini::error::CmdError;
 use serde_json::Value;
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::commands::parse_response;
 use crate::capabilities::android::AndroidCapabilities;
 use crate::capabilities::AppiumCapability;
 use crate::capabilities::ios::IOSCapabilities;
@@ -17,10 +18,8 @@ pub trait HasBattery<Caps>: AppiumClientTrait
 {
     async fn battery_info(&self) -> Result<BatteryInfo<Caps>, CmdError> {
         let value = self.execute("mobile: batteryInfo", vec![]).await?;
-        Ok(BatteryInfo {
-            inner: serde_json::from_value(value)?,
-            caps: PhantomData,
-        })
+        let info: HashMap<String, Value> = parse_response("mobile: batteryInfo", value)?;
+        Ok(BatteryInfo::from(info))
     }
 }
 
@@ -37,13 +36,28 @@ pub struct BatteryInfo<Caps>
     caps: PhantomData<Caps>,
 }
 
+impl<Caps> From<HashMap<String, Value>> for BatteryInfo<Caps>
+    where Caps: AppiumCapability {
+    fn from(inner: HashMap<String, Value>) -> Self {
+        BatteryInfo {
+            inner,
+            caps: PhantomData,
+        }
+    }
+}
+
 impl<Caps> BatteryInfo<Caps>
     where Caps: AppiumCapability {
 
+    /// Battery level, from 0.0 to 1.0.
+    ///
+    /// Unknown level is reported as 0.0. iOS simulators (and devices with battery monitoring off) send -1 instead of the level,
+    /// use [BatteryInfo::is_available] to tell it apart from an empty battery.
     pub fn level(&self) -> f64 {
         self.get("level")
             .cloned()
             .and_then(|v| serde_json::from_value(v).ok())
+            .filter(|level: &f64| *level >= 0.0)
             .unwrap_or(0f64)
     }
 }
@@ -129,6 +143,36 @@ impl BatteryInfo<IOSCapabilities> {
             })
             .unwrap_or(IOSBatteryState::Unknown)
     }
+
+    /// Whether the battery level is known.
+    ///
+    /// iOS reports level -1 if it can't tell (e.g. on simulators), so use it to tell "unknown" from "0%".
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use serde_json::{json, Value};
+    /// use appium_client::capabilities::ios::IOSCapabilities;
+    /// use appium_client::commands::battery::BatteryInfo;
+    ///
+    /// let simulator: BatteryInfo<IOSCapabilities> = HashMap::from([
+    ///     ("level".to_string(), json!(-1)),
+    ///     ("state".to_string(), json!(0)),
+    /// ]).into();
+    /// assert!(!simulator.is_available());
+    /// assert_eq!(simulator.level(), 0.0);
+    ///
+    /// let empty: BatteryInfo<IOSCapabilities> = HashMap::from([
+    ///     ("level".to_string(), json!(0.0)),
+    ///     ("state".to_string(), json!(1)),
+    /// ]).into();
+    /// assert!(empty.is_available());
+    /// assert_eq!(empty.level(), 0.0);
+    /// ```
+    pub fn is_available(&self) -> bool {
+        self.get("level")
+            .and_then(|v| v.as_f64())
+            .is_some_and(|level| level >= 0.0)
+    }
 }
 
 impl CanBeCharged for BatteryInfo<IOSCapabilities> {
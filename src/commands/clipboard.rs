@@ -1,4 +1,6 @@
 //! Clipboard management
+use std::path::Path;
+use std::time::Duration;
 use async_trait::async_trait;
 use base64::Engine;
 use base64::engine::general_purpose;
@@ -6,9 +8,10 @@ use fantoccini::error::CmdError;
 use http::Method;
 use serde_derive::Serialize;
 use serde_json::json;
+use tokio::time::sleep;
 
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 #[derive(Copy, Clone, Serialize, Debug, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -18,23 +21,24 @@ pub enum ClipboardContentType {
     URL,
 }
 
+/// Content of the clipboard, decoded according to its type (see [HasClipboard::get_clipboard_any])
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClipboardContent {
+    Text(String),
+    /// Image data (PNG)
+    Image(Vec<u8>),
+    Url(String),
+}
+
 /// Retrieve and save data in device's clipboard
 #[async_trait]
 pub trait HasClipboard: AppiumClientTrait {
+    /// Gets clipboard content (decoded from base64).
+    ///
+    /// Note: on iOS, the clipboard can be read only if the app under test is in foreground.
+    /// If it's not, the error message is extended with a hint (see [clipboard_error_hint]).
     async fn get_clipboard(&self, content_type: ClipboardContentType) -> Result<Vec<u8>, CmdError> {
-        let value = self.issue_cmd(AppiumCommand::Custom(
-            Method::POST,
-            "appium/device/get_clipboard".to_string(),
-            Some(json!({
-                "contentType": content_type
-            })),
-        )).await?;
-
-        let base64: String = serde_json::from_value::<String>(value)?
-            .replace('\n', "");
-
-        Ok(general_purpose::STANDARD.decode(base64)
-            .map_err(|e| CmdError::NotJson(format!("{e}")))?)
+        fetch_clipboard(self, content_type).await
     }
 
     async fn set_clipboard<CT>(&self, content_type: ClipboardContentType, content: CT) -> Result<(), CmdError>
@@ -65,13 +69,131 @@ pub trait HasClipboard: AppiumClientTrait {
         Ok(String::from_utf8(clipboard)
             .map_err(|e| CmdError::NotJson(format!("{e}")))?)
     }
+
+    /// Gets clipboard content of given type, decoded into [ClipboardContent].
+    ///
+    /// Text and URL are decoded as UTF-8, image is returned as raw bytes.
+    async fn get_clipboard_any(&self, content_type: ClipboardContentType) -> Result<ClipboardContent, CmdError> {
+        let clipboard = self.get_clipboard(content_type).await?;
+        let as_string = |clipboard: Vec<u8>| String::from_utf8(clipboard)
+            .map_err(|e| CmdError::NotJson(format!("{e}")));
+
+        Ok(match content_type {
+            ClipboardContentType::PlainText => ClipboardContent::Text(as_string(clipboard)?),
+            ClipboardContentType::Image => ClipboardContent::Image(clipboard),
+            ClipboardContentType::URL => ClipboardContent::Url(as_string(clipboard)?),
+        })
+    }
+
+    /// Reads a PNG image from local file and puts it into the clipboard.
+    ///
+    /// Returns [CmdError::InvalidArgument] if the file is not a PNG image.
+    async fn set_clipboard_image_from_path(&self, local: &Path) -> Result<(), CmdError> {
+        let image = tokio::fs::read(local).await?;
+
+        if !image.starts_with(PNG_SIGNATURE) {
+            return Err(CmdError::InvalidArgument(
+                "local".to_string(),
+                format!("{} is not a PNG image", local.display())
+            ));
+        }
+
+        self.set_clipboard(ClipboardContentType::Image, image).await
+    }
+
+    /// Puts text into the clipboard and reads it back.
+    ///
+    /// Some devices need a moment to update the clipboard, so if the content differs,
+    /// it's read again ([CLIPBOARD_RETRIES] times at most, every [CLIPBOARD_RETRY_INTERVAL]).
+    /// Returns the last content read, so compare it with `text` to check if the round trip succeeded.
+    async fn roundtrip_clipboard_text(&self, text: &str) -> Result<String, CmdError> {
+        self.set_clipboard_text(text).await?;
+
+        let mut content = self.get_clipboard_text().await?;
+        for _ in 0..CLIPBOARD_RETRIES {
+            if content == text {
+                break;
+            }
+
+            sleep(CLIPBOARD_RETRY_INTERVAL).await;
+            content = self.get_clipboard_text().await?;
+        }
+
+        Ok(content)
+    }
 }
 
+/// How many times [HasClipboard::roundtrip_clipboard_text] reads the clipboard again if it differs.
+pub const CLIPBOARD_RETRIES: usize = 3;
+
+/// Delay between reads of [HasClipboard::roundtrip_clipboard_text].
+pub const CLIPBOARD_RETRY_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Magic bytes at the beginning of every PNG file.
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
 #[async_trait]
 impl HasClipboard for AndroidClient {}
 
 #[async_trait]
-impl HasClipboard for IOSClient {}
+impl HasClipboard for IOSClient {
+    async fn get_clipboard(&self, content_type: ClipboardContentType) -> Result<Vec<u8>, CmdError> {
+        fetch_clipboard(self, content_type).await
+            .map_err(|error| match error {
+                CmdError::Standard(mut error) => {
+                    if let Some(message) = clipboard_error_hint(&error.message) {
+                        error.message = message.into();
+                    }
+                    CmdError::Standard(error)
+                }
+                error => error,
+            })
+    }
+}
+
+/// Gets clipboard content, see [HasClipboard::get_clipboard].
+async fn fetch_clipboard<C>(client: &C, content_type: ClipboardContentType) -> Result<Vec<u8>, CmdError>
+    where C: AppiumClientTrait + Sync + ?Sized
+{
+    let value = client.issue_cmd(AppiumCommand::Custom(
+        Method::POST,
+        "appium/device/get_clipboard".to_string(),
+        Some(json!({
+            "contentType": content_type
+        })),
+    )).await?;
+
+    let base64: String = parse_response::<String>("appium/device/get_clipboard", value)?
+        .replace('\n', "");
+
+    general_purpose::STANDARD.decode(base64)
+        .map_err(|e| CmdError::NotJson(format!("{e}")))
+}
+
+/// Error message of reading clipboard on iOS, extended with a hint if the failure is caused by the app being in background.
+///
+/// iOS lets only the foreground app read the clipboard, and the error of driver doesn't tell what to do about it.
+/// Returns `None` if the message is about something else.
+///
+/// ```
+/// use appium_client::commands::clipboard::clipboard_error_hint;
+///
+/// let message = clipboard_error_hint("Cannot read the pasteboard, the app is not in foreground").unwrap();
+/// assert!(message.starts_with("Cannot read the pasteboard, the app is not in foreground"));
+/// assert!(message.contains("activate_app"));
+///
+/// assert_eq!(clipboard_error_hint("Session does not exist"), None);
+/// ```
+pub fn clipboard_error_hint(message: &str) -> Option<String> {
+    let lowercase = message.to_lowercase();
+    if !lowercase.contains("foreground") && !lowercase.contains("background") {
+        return None;
+    }
+
+    Some(format!(
+        "{message} (iOS lets only the foreground app read the clipboard, bring the app under test to foreground with activate_app first)"
+    ))
+}
 
 #[async_trait]
 pub trait HasAndroidClipboard: HasClipboard {
@@ -100,4 +222,111 @@ pub trait HasAndroidClipboard: HasClipboard {
 }
 
 #[async_trait]
-impl HasAndroidClipboard for AndroidClient {}
\ No newline at end of file
+impl HasAndroidClipboard for AndroidClient {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use base64::Engine;
+    use base64::engine::general_purpose;
+    use fantoccini::error::CmdError;
+    use serde_json::json;
+    use crate::commands::clipboard::{CLIPBOARD_RETRIES, ClipboardContent, ClipboardContentType, HasClipboard, PNG_SIGNATURE};
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    /// Writes a file into temp dir (with a name unique for the test).
+    fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("appium-client-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn png_file_is_put_into_clipboard() {
+        let image = [PNG_SIGNATURE, b"image data"].concat();
+        let path = temp_file("clipboard.png", &image);
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.set_clipboard_image_from_path(&path).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/appium/device/set_clipboard");
+        assert_eq!(request.body.unwrap(), json!({
+            "contentType": "image",
+            "content": general_purpose::STANDARD.encode(&image)
+        }));
+    }
+
+    #[tokio::test]
+    async fn file_that_is_not_png_is_refused() {
+        let path = temp_file("clipboard.jpg", b"\xFF\xD8\xFF\xE0 not a png");
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+        let requests = transport.requests().len();
+
+        let result = client.set_clipboard_image_from_path(&path).await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert_eq!(transport.requests().len(), requests);
+    }
+
+    fn clipboard(content: &[u8]) -> MockResponse {
+        MockResponse::value(json!(general_purpose::STANDARD.encode(content)))
+    }
+
+    #[tokio::test]
+    async fn clipboard_content_is_decoded_by_type() {
+        let transport = MockTransport::new();
+        transport.respond(clipboard(b"hello"));
+        transport.respond(clipboard(PNG_SIGNATURE));
+        transport.respond(clipboard(b"https://example.com"));
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.get_clipboard_any(ClipboardContentType::PlainText).await.unwrap(), ClipboardContent::Text("hello".to_string()));
+        assert_eq!(client.get_clipboard_any(ClipboardContentType::Image).await.unwrap(), ClipboardContent::Image(PNG_SIGNATURE.to_vec()));
+        assert_eq!(client.get_clipboard_any(ClipboardContentType::URL).await.unwrap(), ClipboardContent::Url("https://example.com".to_string()));
+        assert_eq!(transport.requests().pop().unwrap().body.unwrap(), json!({ "contentType": "url" }));
+    }
+
+    #[tokio::test]
+    async fn text_that_is_not_utf8_is_an_error() {
+        let transport = MockTransport::new();
+        transport.respond(clipboard(&[0xFF, 0xFE]));
+        let client = android_client(&transport).await;
+
+        assert!(client.get_clipboard_any(ClipboardContentType::PlainText).await.is_err());
+    }
+
+    fn clipboard_reads(transport: &MockTransport) -> usize {
+        transport.requests_to("/get_clipboard").len()
+    }
+
+    #[tokio::test]
+    async fn clipboard_is_read_again_until_updated() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(clipboard(b"old"));
+        transport.respond(clipboard(b"new"));
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.roundtrip_clipboard_text("new").await.unwrap(), "new");
+        assert_eq!(clipboard_reads(&transport), 2);
+    }
+
+    #[tokio::test]
+    async fn last_content_is_returned_after_retries() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        for _ in 0..=CLIPBOARD_RETRIES {
+            transport.respond(clipboard(b"old"));
+        }
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.roundtrip_clipboard_text("new").await.unwrap(), "old");
+        assert_eq!(clipboard_reads(&transport), CLIPBOARD_RETRIES + 1);
+    }
+}
@@ -1,10 +1,13 @@
 //! Context API (<https://appium.io/docs/en/2.1/guides/context/>)
+use std::time::Duration;
 use async_trait::async_trait;
 use fantoccini::error::CmdError;
 use http::Method;
-use serde_json::json;
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use tokio::time::{Instant, interval};
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 /// Check current context or switch context (<https://appium.io/docs/en/2.1/guides/context/>)
 #[async_trait]
@@ -25,7 +28,7 @@ pub trait SupportsContextSwitching: AppiumClientTrait {
             None
         )).await?;
 
-        let value: Option<String> = serde_json::from_value(value)?;
+        let value: Option<String> = parse_response("context", value)?;
         Ok(value.and_then(|v| if v != "null" {
             Some(v)
         } else {
@@ -40,13 +43,334 @@ pub trait SupportsContextSwitching: AppiumClientTrait {
             None
         )).await?;
 
-        let value: Vec<String> = serde_json::from_value(value)?;
+        let value: Vec<String> = parse_response("contexts", value)?;
         Ok(value)
     }
+
+    /// Lists available contexts with details (like url and title of webviews).
+    ///
+    /// This uses `mobile: getContexts`.
+    async fn detailed_contexts(&self) -> Result<Vec<DetailedContext>, CmdError> {
+        let value = self.execute("mobile: getContexts", vec![]).await?;
+        DetailedContext::from_response(value)
+    }
+
+    /// Same as [SupportsContextSwitching::available_contexts], but the server waits (at most `wait`) for a webview to appear.
+    ///
+    /// This uses `mobile: getContexts` with `waitForWebviewMs`, so there's no need to poll contexts from the client.
+    /// UiAutomator2 lists only webviews there, so [NATIVE_CONTEXT] is added first if the driver didn't list it.
+    async fn available_contexts_waiting(&self, wait: Duration) -> Result<Vec<String>, CmdError> {
+        let value = self.execute("mobile: getContexts", vec![json!({
+            "waitForWebviewMs": wait.as_millis() as u64
+        })]).await?;
+
+        let mut contexts: Vec<String> = DetailedContext::from_response(value)?
+            .into_iter()
+            .map(|context| context.id)
+            .collect();
+
+        if !contexts.iter().any(|context| context == NATIVE_CONTEXT) {
+            contexts.insert(0, NATIVE_CONTEXT.to_string());
+        }
+
+        Ok(contexts)
+    }
+
+    /// Waits until a webview context with a loaded page (non-empty url) appears, then returns its id.
+    ///
+    /// Hybrid apps often expose the webview context before the page is actually loaded,
+    /// so just waiting for the context to appear is usually not enough.
+    async fn wait_for_webview(&self, timeout: Duration) -> Result<String, CmdError> {
+        let mut interval = interval(Duration::from_millis(250));
+        let start = Instant::now();
+
+        loop {
+            if start.elapsed() > timeout {
+                return Err(CmdError::WaitTimeout);
+            }
+
+            let webview = self.detailed_contexts().await?
+                .into_iter()
+                .find(|context| context.is_webview() && context.url.as_ref().is_some_and(|url| !url.is_empty()));
+
+            if let Some(webview) = webview {
+                return Ok(webview.id);
+            }
+
+            interval.tick().await;
+        }
+    }
+
+    /// Switches to `FLUTTER` context of Appium Flutter driver, so that Flutter locators (e.g. [crate::find::By::flutter_key]) can be used.
+    async fn switch_to_flutter_context(&self) -> Result<(), CmdError> {
+        self.set_context(FLUTTER_CONTEXT).await
+    }
+
+    /// Checks whether the current context is native or a webview.
+    async fn current_context_kind(&self) -> Result<ContextKind, CmdError> {
+        Ok(match self.current_context().await? {
+            Some(context) if is_webview_context(&context) => ContextKind::Webview,
+            _ => ContextKind::Native,
+        })
+    }
+
+    /// Url of the page loaded in current webview.
+    ///
+    /// Same as `current_url`, but returns [CmdError::InvalidArgument] if current context is not a webview
+    /// (instead of whatever the driver responds with in native context).
+    async fn webview_url(&self) -> Result<String, CmdError> {
+        require_webview(self, "Reading url").await?;
+        Ok(self.current_url().await?.to_string())
+    }
+
+    /// Deletes all cookies of current webview.
+    ///
+    /// Same as `delete_all_cookies`, but returns [CmdError::InvalidArgument] if current context is not a webview.
+    async fn clear_webview_cookies(&self) -> Result<(), CmdError> {
+        require_webview(self, "Clearing cookies").await?;
+        self.delete_all_cookies().await
+    }
 }
 
 #[async_trait]
 impl SupportsContextSwitching for AndroidClient {}
 
 #[async_trait]
-impl SupportsContextSwitching for IOSClient {}
\ No newline at end of file
+impl SupportsContextSwitching for IOSClient {}
+
+/// Native context of the app, available on every platform.
+pub const NATIVE_CONTEXT: &str = "NATIVE_APP";
+
+/// Context of Flutter driver.
+pub const FLUTTER_CONTEXT: &str = "FLUTTER";
+
+/// Context with details, as returned by `mobile: getContexts`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DetailedContext {
+    /// Context name (e.g. `NATIVE_APP` or `WEBVIEW_1`), to be used with [SupportsContextSwitching::set_context].
+    pub id: String,
+    /// Title of the page loaded in webview.
+    pub title: Option<String>,
+    /// Url of the page loaded in webview.
+    pub url: Option<String>,
+    /// Bundle id of the app owning webview (iOS only).
+    pub bundle_id: Option<String>,
+}
+
+impl DetailedContext {
+    pub fn is_webview(&self) -> bool {
+        is_webview_context(&self.id)
+    }
+
+    /// Reads contexts from `mobile: getContexts` response.
+    ///
+    /// XCUITest returns a list of contexts, while UiAutomator2 returns a list of webviews with pages.
+    /// For the latter, one context per webview is returned (with url and title of its first page).
+    fn from_response(value: Value) -> Result<Vec<DetailedContext>, CmdError> {
+        let contexts: Vec<Value> = parse_response("mobile: getContexts", value)?;
+
+        contexts.into_iter()
+            .map(|context| match context.get("webviewName") {
+                Some(name) => {
+                    let page = context.get("pages")
+                        .and_then(|pages| pages.get(0))
+                        .cloned()
+                        .unwrap_or_else(|| json!({}));
+
+                    Ok(DetailedContext {
+                        id: parse_response("mobile: getContexts", name.clone())?,
+                        title: parse_response("mobile: getContexts", page["title"].clone())?,
+                        url: parse_response("mobile: getContexts", page["url"].clone())?,
+                        bundle_id: None,
+                    })
+                }
+                None => parse_response("mobile: getContexts", context),
+            })
+            .collect()
+    }
+}
+
+/// Kind of context, see [SupportsContextSwitching::current_context_kind]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ContextKind {
+    Native,
+    Webview,
+}
+
+fn is_webview_context(name: &str) -> bool {
+    name.starts_with("WEBVIEW") || name.starts_with("CHROMIUM")
+}
+
+/// Returns an error if the current context is not a webview (`what` is the action that needs a webview).
+async fn require_webview<C>(client: &C, what: &str) -> Result<(), CmdError>
+    where C: SupportsContextSwitching + Sync + ?Sized
+{
+    match client.current_context().await? {
+        Some(context) if is_webview_context(&context) => Ok(()),
+        context => Err(CmdError::InvalidArgument(
+            "context".to_string(),
+            format!(
+                "{what} requires a webview context, but current context is {}. Switch to a webview with set_context first.",
+                context.as_deref().unwrap_or(NATIVE_CONTEXT)
+            )
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use serde_json::json;
+    use fantoccini::error::CmdError;
+    use http::Method;
+    use crate::commands::contexts::{ContextKind, DetailedContext, SupportsContextSwitching};
+    use crate::mock::{android_client, ios_client, MockResponse, MockTransport};
+
+    fn android_webview(url: &str) -> serde_json::Value {
+        json!([{
+            "webviewName": "WEBVIEW_com.example",
+            "pages": [{ "id": "1", "title": "Login", "url": url }]
+        }])
+    }
+
+    #[tokio::test]
+    async fn android_webviews_are_read_with_their_first_page() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(android_webview("https://example.com/login")));
+        let client = android_client(&transport).await;
+
+        let contexts = client.detailed_contexts().await.unwrap();
+
+        assert_eq!(contexts, vec![DetailedContext {
+            id: "WEBVIEW_com.example".to_string(),
+            title: Some("Login".to_string()),
+            url: Some("https://example.com/login".to_string()),
+            bundle_id: None,
+        }]);
+    }
+
+    #[tokio::test]
+    async fn ios_contexts_are_read_as_they_are() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!([
+            { "id": "NATIVE_APP" },
+            { "id": "WEBVIEW_1.2", "title": "Login", "url": "https://example.com/login", "bundleId": "com.example" }
+        ])));
+        let client = ios_client(&transport).await;
+
+        let contexts = client.detailed_contexts().await.unwrap();
+
+        assert_eq!(contexts.len(), 2);
+        assert_eq!(contexts[0].id, "NATIVE_APP");
+        assert!(!contexts[0].is_webview());
+        assert_eq!(contexts[1].bundle_id.as_deref(), Some("com.example"));
+        assert!(contexts[1].is_webview());
+    }
+
+    #[tokio::test]
+    async fn wait_for_webview_waits_for_loaded_page() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(android_webview("")));
+        transport.respond(MockResponse::value(android_webview("https://example.com/login")));
+        let client = android_client(&transport).await;
+
+        let webview = client.wait_for_webview(Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(webview, "WEBVIEW_com.example");
+        let polls = transport.requests_to("/execute/sync").len();
+        assert_eq!(polls, 2);
+    }
+
+    #[tokio::test]
+    async fn server_waits_for_webview_contexts() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!([{ "id": "NATIVE_APP" }, { "id": "WEBVIEW_1.2" }])));
+        let client = ios_client(&transport).await;
+
+        let contexts = client.available_contexts_waiting(Duration::from_secs(3)).await.unwrap();
+
+        assert_eq!(contexts, vec!["NATIVE_APP", "WEBVIEW_1.2"]);
+        assert_eq!(transport.requests().pop().unwrap().body.unwrap(), json!({
+            "script": "mobile: getContexts",
+            "args": [{ "waitForWebviewMs": 3000 }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn native_context_is_added_to_android_webviews() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(android_webview("https://example.com/login")));
+        let client = android_client(&transport).await;
+
+        let contexts = client.available_contexts_waiting(Duration::from_secs(3)).await.unwrap();
+
+        assert_eq!(contexts, vec!["NATIVE_APP", "WEBVIEW_com.example"]);
+        assert_eq!(transport.requests().pop().unwrap().body.unwrap(), json!({
+            "script": "mobile: getContexts",
+            "args": [{ "waitForWebviewMs": 3000 }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn context_kind_is_read_from_context_name() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("NATIVE_APP")));
+        transport.respond(MockResponse::value(json!("CHROMIUM")));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.current_context_kind().await.unwrap(), ContextKind::Native);
+        assert_eq!(client.current_context_kind().await.unwrap(), ContextKind::Webview);
+        assert_eq!(client.current_context_kind().await.unwrap(), ContextKind::Native);
+    }
+
+    #[tokio::test]
+    async fn webview_url_is_read_in_webview() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("WEBVIEW_com.example")));
+        transport.respond(MockResponse::value(json!("https://example.com/login")));
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.webview_url().await.unwrap(), "https://example.com/login");
+        assert_eq!(transport.requests().pop().unwrap().path, "/session/mock-session/url");
+    }
+
+    #[tokio::test]
+    async fn webview_url_is_refused_in_native_context() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("NATIVE_APP")));
+        let client = android_client(&transport).await;
+
+        let result = client.webview_url().await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert_eq!(transport.requests().pop().unwrap().path, "/session/mock-session/context");
+    }
+
+    #[tokio::test]
+    async fn webview_cookies_are_deleted_in_webview() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("WEBVIEW_1.2")));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.clear_webview_cookies().await.unwrap();
+
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(request.path, "/session/mock-session/cookie");
+    }
+
+    #[tokio::test]
+    async fn cookies_are_not_deleted_in_native_context() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("NATIVE_APP")));
+        let client = ios_client(&transport).await;
+
+        let result = client.clear_webview_cookies().await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert!(transport.requests_to("/cookie").is_empty());
+    }
+}
@@ -6,7 +6,7 @@ use fantoccini::error::CmdError;
 use http::Method;
 use serde_json::json;
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 /// Download files and folders from the device (to your computer)
 #[async_trait]
@@ -22,7 +22,7 @@ pub trait PullsFiles : AppiumClientTrait {
             }))
         )).await?;
 
-        let value: String = serde_json::from_value(value)?;
+        let value: String = parse_response("appium/device/pull_file", value)?;
 
         Ok(general_purpose::STANDARD.decode(value)
             .map_err(|e| CmdError::NotJson(format!("{e}")))?)
@@ -38,7 +38,7 @@ pub trait PullsFiles : AppiumClientTrait {
             }))
         )).await?;
 
-        let value: String = serde_json::from_value(value)?;
+        let value: String = parse_response("appium/device/pull_folder", value)?;
 
         Ok(general_purpose::STANDARD.decode(value)
             .map_err(|e| CmdError::NotJson(format!("{e}")))?)
@@ -0,0 +1,724 @@
+//! Common gestures (swipes, taps) built from W3C actions
+use std::time::Duration;
+use async_trait::async_trait;
+use fantoccini::actions::{InputSource, MOUSE_BUTTON_LEFT, PointerAction, TouchActions};
+use fantoccini::elements::Element;
+use fantoccini::error::CmdError;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::commands::window::{HasWindowSize, point_in, usable_rect, WindowSize};
+use crate::find::{AppiumFind, By};
+use crate::wait::AppiumWait;
+
+/// Direction in which the finger moves during a swipe.
+///
+/// Note: swiping [SwipeDirection::Up] scrolls the content down (reveals what's below), just like on a phone.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeDirection {
+    /// Start and end of a swipe through most of the window, as fractions of the window size.
+    pub fn fractions(&self) -> ((f64, f64), (f64, f64)) {
+        match self {
+            SwipeDirection::Up => ((0.5, 0.8), (0.5, 0.2)),
+            SwipeDirection::Down => ((0.5, 0.2), (0.5, 0.8)),
+            SwipeDirection::Left => ((0.8, 0.5), (0.2, 0.5)),
+            SwipeDirection::Right => ((0.2, 0.5), (0.8, 0.5)),
+        }
+    }
+
+    /// Start and end of a swipe through one page (90% to 10% of the window), as fractions of the window size.
+    pub fn page_fractions(&self) -> ((f64, f64), (f64, f64)) {
+        match self {
+            SwipeDirection::Up => ((0.5, 0.9), (0.5, 0.1)),
+            SwipeDirection::Down => ((0.5, 0.1), (0.5, 0.9)),
+            SwipeDirection::Left => ((0.9, 0.5), (0.1, 0.5)),
+            SwipeDirection::Right => ((0.1, 0.5), (0.9, 0.5)),
+        }
+    }
+}
+
+/// A pause of a finger (pointer), e.g. to let the app settle between gestures.
+///
+/// ```
+/// use std::time::Duration;
+/// use fantoccini::actions::{InputSource, MOUSE_BUTTON_LEFT, PointerAction, TouchActions};
+/// use appium_client::commands::gestures::pause;
+///
+/// let tap_twice_slowly = TouchActions::new("finger".to_string())
+///     .then(PointerAction::Down { button: MOUSE_BUTTON_LEFT })
+///     .then(PointerAction::Up { button: MOUSE_BUTTON_LEFT })
+///     .then(pause(Duration::from_millis(500)))
+///     .then(PointerAction::Down { button: MOUSE_BUTTON_LEFT })
+///     .then(PointerAction::Up { button: MOUSE_BUTTON_LEFT });
+/// ```
+pub fn pause(duration: Duration) -> PointerAction {
+    PointerAction::Pause {
+        duration
+    }
+}
+
+/// How the finger speed changes during a swipe.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum VelocityProfile {
+    /// Constant speed (one move).
+    #[default]
+    Linear,
+    /// Fast at the beginning, then slowing down, like a fling.
+    ///
+    /// The move is split into [EASE_OUT_STEPS] sub-moves of equal duration, but decreasing distance.
+    EaseOut,
+}
+
+/// Number of sub-moves of [VelocityProfile::EaseOut].
+pub const EASE_OUT_STEPS: usize = 5;
+
+impl VelocityProfile {
+    /// Moves of a swipe from one point to another, according to this profile.
+    ///
+    /// The last move always ends at `to`.
+    pub fn moves(&self, from: (i64, i64), to: (i64, i64), duration: Duration) -> Vec<PointerAction> {
+        match self {
+            VelocityProfile::Linear => vec![PointerAction::MoveTo {
+                duration: Some(duration),
+                x: to.0,
+                y: to.1,
+            }],
+            VelocityProfile::EaseOut => {
+                let step = duration / EASE_OUT_STEPS as u32;
+                let (dx, dy) = ((to.0 - from.0) as f64, (to.1 - from.1) as f64);
+
+                (1..=EASE_OUT_STEPS)
+                    .map(|i| {
+                        // quadratic ease-out: 1 - (1 - t)^2
+                        let t = i as f64 / EASE_OUT_STEPS as f64;
+                        let progress = 1.0 - (1.0 - t).powi(2);
+
+                        PointerAction::MoveTo {
+                            duration: Some(step),
+                            x: from.0 + (dx * progress).round() as i64,
+                            y: from.1 + (dy * progress).round() as i64,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Swipe gesture from one point to another (one finger).
+pub(crate) fn swipe_actions(from: (i64, i64), to: (i64, i64), duration: Duration, profile: VelocityProfile) -> TouchActions {
+    let finger = TouchActions::new("finger".to_string())
+        .then(PointerAction::MoveTo {
+            duration: Some(Duration::from_millis(0)),
+            x: from.0,
+            y: from.1,
+        })
+        .then(PointerAction::Down {
+            button: MOUSE_BUTTON_LEFT
+        });
+
+    profile.moves(from, to, duration)
+        .into_iter()
+        .fold(finger, |finger, action| finger.then(action))
+        .then(PointerAction::Up {
+            button: MOUSE_BUTTON_LEFT
+        })
+}
+
+/// Tap gesture (or many taps) at a point.
+pub(crate) fn tap_actions(x: i64, y: i64, taps: usize) -> TouchActions {
+    let finger = TouchActions::new("finger".to_string())
+        .then(PointerAction::MoveTo {
+            duration: Some(Duration::from_millis(0)),
+            x,
+            y,
+        });
+
+    (0..taps).fold(finger, |finger, tap| {
+        let finger = if tap > 0 {
+            finger.then(pause(Duration::from_millis(100)))
+        } else {
+            finger
+        };
+
+        finger
+            .then(PointerAction::Down {
+                button: MOUSE_BUTTON_LEFT
+            })
+            .then(PointerAction::Up {
+                button: MOUSE_BUTTON_LEFT
+            })
+    })
+}
+
+/// Long press gesture (press, hold, release) at a point.
+pub(crate) fn long_press_actions(x: i64, y: i64, hold: Duration) -> TouchActions {
+    TouchActions::new("finger".to_string())
+        .then(PointerAction::MoveTo {
+            duration: Some(Duration::from_millis(0)),
+            x,
+            y,
+        })
+        .then(PointerAction::Down {
+            button: MOUSE_BUTTON_LEFT
+        })
+        .then(pause(hold))
+        .then(PointerAction::Up {
+            button: MOUSE_BUTTON_LEFT
+        })
+}
+
+fn validate_point(x: i64, y: i64) -> Result<(), CmdError> {
+    if x < 0 || y < 0 {
+        return Err(CmdError::InvalidArgument(
+            "(x, y)".to_string(),
+            format!("({x}, {y}) should not be negative")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Tap on the screen at given coordinates
+#[async_trait]
+pub trait TapsAt: AppiumClientTrait {
+    /// Taps a point on screen (in pixels), e.g. a point on a map.
+    ///
+    /// Only negative coordinates are rejected. If you want to make sure the point is on screen,
+    /// check it with [crate::commands::window::WindowSize::contains].
+    async fn tap_at(&self, x: i64, y: i64) -> Result<(), CmdError> {
+        validate_point(x, y)?;
+        self.perform_actions(tap_actions(x, y, 1)).await
+    }
+
+    /// Taps a point on screen (in pixels) twice.
+    async fn double_tap_at(&self, x: i64, y: i64) -> Result<(), CmdError> {
+        validate_point(x, y)?;
+        self.perform_actions(tap_actions(x, y, 2)).await
+    }
+}
+
+#[async_trait]
+impl TapsAt for AndroidClient {}
+
+#[async_trait]
+impl TapsAt for IOSClient {}
+
+/// Long press elements (e.g. to open a context menu)
+#[async_trait]
+pub trait LongPresses: AppiumClientTrait {
+    /// Presses the center of the element and holds it for `hold`.
+    async fn long_press_element(&self, target: &Element, hold: Duration) -> Result<(), CmdError> {
+        let (x, y, width, height) = target.rectangle().await?;
+        self.perform_actions(long_press_actions((x + width / 2.0) as i64, (y + height / 2.0) as i64, hold)).await
+    }
+
+    /// Long presses the target, waits until `menu_item` appears and taps it, e.g. to pick "Delete" from a context menu.
+    ///
+    /// The menu item is awaited with the default timeout of [crate::wait::Wait].
+    ///
+    /// ```no_run
+    ///# use std::time::Duration;
+    ///# use appium_client::AndroidClient;
+    ///# use appium_client::commands::gestures::LongPresses;
+    ///# use appium_client::find::{AppiumFind, By};
+    ///# async fn delete(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// let message = client.find_by(By::id("message")).await?;
+    /// client.long_press_then_select(&message, By::accessibility_id("Delete"), Duration::from_secs(1)).await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    async fn long_press_then_select(&self, target: &Element, menu_item: By, hold: Duration) -> Result<(), CmdError> {
+        self.long_press_element(target, hold).await?;
+
+        self.appium_wait()
+            .for_element(menu_item)
+            .await?
+            .click()
+            .await
+    }
+}
+
+#[async_trait]
+impl LongPresses for AndroidClient {}
+
+#[async_trait]
+impl LongPresses for IOSClient {}
+
+/// Swipe through the screen
+#[async_trait]
+pub trait Swipes: AppiumClientTrait + HasWindowSize {
+    /// Swipes from `start` to `end`, where both points are fractions (0.0..=1.0) of the window size.
+    ///
+    /// So instead of calculating pixels yourself, you can swipe up through most of the screen like this:
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use appium_client::AndroidClient;
+    /// # use appium_client::commands::gestures::Swipes;
+    /// # async fn swipe(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// client.swipe_by_percent((0.5, 0.8), (0.5, 0.2), Duration::from_millis(500)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn swipe_by_percent(&self, start: (f64, f64), end: (f64, f64), duration: Duration) -> Result<(), CmdError> {
+        self.swipe_by_percent_with_profile(start, end, duration, VelocityProfile::Linear).await
+    }
+
+    /// Same as [Swipes::swipe_by_percent], but the finger speed changes according to `profile`.
+    ///
+    /// Use [VelocityProfile::EaseOut] for more realistic flings (some apps scroll differently on a constant-speed move).
+    async fn swipe_by_percent_with_profile(&self, start: (f64, f64), end: (f64, f64), duration: Duration, profile: VelocityProfile) -> Result<(), CmdError> {
+        let size = self.window_size().await?;
+        let from = size.point_at(start)?;
+        let to = size.point_at(end)?;
+
+        self.perform_actions(swipe_actions(from, to, duration, profile)).await
+    }
+
+    /// Swipes through most of the window in given direction.
+    async fn swipe(&self, direction: SwipeDirection, duration: Duration) -> Result<(), CmdError> {
+        let (start, end) = direction.fractions();
+        self.swipe_by_percent(start, end, duration).await
+    }
+
+    /// Scrolls by (roughly) one page, swiping from 90% to 10% of the window in given direction.
+    ///
+    /// The swipe is done within the part of the window that is not covered by system bars (on Android),
+    /// so it doesn't start on the navigation bar (or pull down the notifications).
+    /// The swipe is slow enough not to fling, so the content moves by the distance of the swipe.
+    /// Note: [SwipeDirection::Up] shows the next page below.
+    async fn scroll_page(&self, direction: SwipeDirection) -> Result<(), CmdError> {
+        let rect = usable_rect(self).await?;
+        let (start, end) = direction.page_fractions();
+
+        self.perform_actions(swipe_actions(point_in(rect, start), point_in(rect, end), Duration::from_millis(1000), VelocityProfile::Linear)).await
+    }
+
+    /// Swipes until `predicate` is satisfied by page source, but no more than `max_swipes` times.
+    ///
+    /// The predicate is checked before the first swipe and after each swipe.
+    /// Returns `Ok(true)` if the predicate was satisfied and `Ok(false)` if max swipes were reached.
+    ///
+    /// Useful if you can't express what you're looking for as a single locator:
+    /// ```no_run
+    /// # use appium_client::AndroidClient;
+    /// # use appium_client::commands::gestures::{SwipeDirection, Swipes};
+    /// # async fn scroll(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// let found = client.scroll_until(SwipeDirection::Up, 10, |source| source.contains("$")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn scroll_until<F>(&self, direction: SwipeDirection, max_swipes: u32, predicate: F) -> Result<bool, CmdError>
+        where F: Fn(&str) -> bool + Send + Sync
+    {
+        if predicate(&self.source().await?) {
+            return Ok(true);
+        }
+
+        for _ in 0..max_swipes {
+            self.swipe(direction, Duration::from_millis(500)).await?;
+
+            if predicate(&self.source().await?) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Scrolls the content so that the element is (roughly) in the center of the window, e.g. before taking a screenshot.
+    ///
+    /// The swipe is slow enough not to fling. It's limited to the window, so an element far away might need a few calls.
+    /// The element must be already located, so use it after the element becomes visible (or at least present in the source).
+    async fn center_element(&self, element: &Element) -> Result<(), CmdError> {
+        let (x, y, width, height) = element.rectangle().await?;
+        let element_center = ((x + width / 2.0) as i64, (y + height / 2.0) as i64);
+
+        let size = self.window_size().await?;
+        let Some((from, to)) = centering_swipe(&size, element_center) else {
+            // already (nearly) centered
+            return Ok(());
+        };
+
+        self.perform_actions(swipe_actions(from, to, Duration::from_millis(1000), VelocityProfile::Linear)).await
+    }
+}
+
+/// Shortest move (in pixels) that is a swipe. Shorter moves are taken for a tap (or a long press, if slow) by the system
+/// (it's the touch slop of Android, iOS is similar).
+const TOUCH_SLOP: i64 = 10;
+
+/// Swipe (from, to) that moves the content by the distance between `element_center` and center of the window.
+///
+/// The swipe starts in the center of the window and ends within the window (so it may be shorter than needed).
+/// Returns `None` if the swipe would be shorter than [TOUCH_SLOP] (the element is nearly centered).
+pub(crate) fn centering_swipe(size: &WindowSize, element_center: (i64, i64)) -> Option<((i64, i64), (i64, i64))> {
+    let (width, height) = (size.width as i64, size.height as i64);
+    let center = (width / 2, height / 2);
+
+    let to = (
+        (center.0 * 2 - element_center.0).clamp(0, (width - 1).max(0)),
+        (center.1 * 2 - element_center.1).clamp(0, (height - 1).max(0)),
+    );
+
+    let (dx, dy) = ((to.0 - center.0) as f64, (to.1 - center.1) as f64);
+    if dx.hypot(dy) < TOUCH_SLOP as f64 {
+        return None;
+    }
+
+    Some((center, to))
+}
+
+#[async_trait]
+impl Swipes for AndroidClient {}
+
+#[async_trait]
+impl Swipes for IOSClient {}
+
+/// Check whether a scrollable view (e.g. a list) is scrolled to the end
+#[async_trait]
+pub trait DetectsScrollEnd: Swipes + AppiumFind {
+    /// Checks whether the content of `scrollable` can't be scrolled any further down (the end of a list).
+    ///
+    /// Drivers don't report scroll position, so this does a short, slow swipe up within the scrollable (from 60% to 40% of its height)
+    /// and compares page source before and after. If the source changed, the content moved,
+    /// so the swipe is reversed to restore the position and `false` is returned.
+    ///
+    /// Note: content that changes on its own (e.g. a clock or an animation) makes the list look like it's never at the end.
+    async fn at_scroll_end(&self, scrollable: By) -> Result<bool, CmdError> {
+        let rect = self.find_by(scrollable).await?.rectangle().await?;
+        let (from, to) = scroll_probe(rect);
+
+        let before = self.source().await?;
+        self.perform_actions(swipe_actions(from, to, Duration::from_millis(500), VelocityProfile::Linear)).await?;
+        let after = self.source().await?;
+
+        if before == after {
+            return Ok(true);
+        }
+
+        self.perform_actions(swipe_actions(to, from, Duration::from_millis(500), VelocityProfile::Linear)).await?;
+        Ok(false)
+    }
+}
+
+/// Short swipe (from, to) up within an element (`(x, y, width, height)`), from 60% to 40% of its height.
+pub(crate) fn scroll_probe(rect: (f64, f64, f64, f64)) -> ((i64, i64), (i64, i64)) {
+    let (x, y, width, height) = rect;
+    let center_x = (x + width / 2.0) as i64;
+
+    ((center_x, (y + height * 0.6) as i64), (center_x, (y + height * 0.4) as i64))
+}
+
+#[async_trait]
+impl DetectsScrollEnd for AndroidClient {}
+
+#[async_trait]
+impl DetectsScrollEnd for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use fantoccini::actions::{InputSource, MOUSE_BUTTON_LEFT, PointerAction, TouchActions};
+    use fantoccini::elements::{Element, ElementRef};
+    use fantoccini::error::CmdError;
+    use http::StatusCode;
+    use serde_json::json;
+    use crate::commands::gestures::{centering_swipe, DetectsScrollEnd, LongPresses, pause, scroll_probe, SwipeDirection, Swipes, TapsAt, VelocityProfile};
+    use crate::find::By;
+    use crate::mock::{android_client, ios_client, MockResponse, MockTransport};
+    use crate::commands::window::WindowSize;
+
+    #[test]
+    fn centering_swipe_moves_content_towards_center() {
+        let size = WindowSize::new(1000, 2000);
+
+        // element below the center, so the content needs to move up
+        assert_eq!(centering_swipe(&size, (500, 1600)), Some(((500, 1000), (500, 400))));
+    }
+
+    #[test]
+    fn centering_swipe_ends_within_window() {
+        let size = WindowSize::new(1000, 2000);
+
+        assert_eq!(centering_swipe(&size, (2500, 1000)), Some(((500, 1000), (0, 1000))));
+    }
+
+    #[test]
+    fn centering_swipe_skips_moves_within_touch_slop() {
+        let size = WindowSize::new(1000, 2000);
+
+        assert_eq!(centering_swipe(&size, (500, 1000)), None);
+        assert_eq!(centering_swipe(&size, (504, 1006)), None);
+        assert!(centering_swipe(&size, (500, 1012)).is_some());
+    }
+
+    #[test]
+    fn scroll_probe_swipes_up_within_element() {
+        assert_eq!(scroll_probe((0.0, 200.0, 1000.0, 1000.0)), ((500, 800), (500, 600)));
+        assert_eq!(scroll_probe((100.0, 0.0, 200.0, 500.0)), ((200, 300), (200, 200)));
+    }
+
+    fn window_rect() -> MockResponse {
+        MockResponse::value(json!({ "x": 0, "y": 0, "width": 1080, "height": 2400 }))
+    }
+
+    fn system_bars() -> MockResponse {
+        MockResponse::value(json!({
+            "statusBar": { "visible": true, "x": 0, "y": 0, "width": 1080, "height": 63 },
+            "navigationBar": { "visible": true, "x": 0, "y": 2274, "width": 1080, "height": 126 }
+        }))
+    }
+
+    /// Points (x, y) of pointer moves in the last actions.
+    fn pointer_moves(transport: &MockTransport) -> Vec<(i64, i64)> {
+        let request = transport.requests_to("/actions").pop()
+            .expect("actions should be performed");
+        let body = request.body.unwrap();
+
+        body["actions"][0]["actions"].as_array().unwrap().iter()
+            .filter(|action| action["type"] == "pointerMove")
+            .map(|action| (action["x"].as_i64().unwrap(), action["y"].as_i64().unwrap()))
+            .collect()
+    }
+
+    /// Types of pointer actions in the last actions (e.g. `pointerDown`).
+    fn pointer_action_types(transport: &MockTransport) -> Vec<String> {
+        let request = transport.requests_to("/actions").pop()
+            .expect("actions should be performed");
+        let body = request.body.unwrap();
+
+        body["actions"][0]["actions"].as_array().unwrap().iter()
+            .map(|action| action["type"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn page_is_scrolled_between_system_bars() {
+        let transport = MockTransport::new();
+        transport.respond(window_rect());
+        transport.respond(system_bars());
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.scroll_page(SwipeDirection::Up).await.unwrap();
+
+        let moves = pointer_moves(&transport);
+        assert_eq!(moves.first(), Some(&(540, 2052)));
+        assert_eq!(moves.last(), Some(&(540, 284)));
+    }
+
+    #[tokio::test]
+    async fn page_is_scrolled_in_whole_window_without_system_bars() {
+        let transport = MockTransport::new();
+        transport.respond(window_rect());
+        transport.respond(MockResponse::error(StatusCode::NOT_FOUND, "unknown command", "system bars are not supported"));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.scroll_page(SwipeDirection::Up).await.unwrap();
+
+        let moves = pointer_moves(&transport);
+        assert_eq!(moves.first(), Some(&(540, 2160)));
+        assert_eq!(moves.last(), Some(&(540, 240)));
+    }
+
+    /// Scripts finding the scrollable and reading its rect.
+    fn scrollable(transport: &MockTransport) {
+        transport.respond(MockResponse::element("list-1"));
+        transport.respond(MockResponse::value(json!({ "x": 0, "y": 200, "width": 1000, "height": 1000 })));
+    }
+
+    fn actions_count(transport: &MockTransport) -> usize {
+        transport.requests_to("/actions").len()
+    }
+
+    #[tokio::test]
+    async fn identical_sources_mean_scroll_end() {
+        let transport = MockTransport::new();
+        scrollable(&transport);
+        transport.respond(MockResponse::value(json!("<hierarchy><node text=\"last\"/></hierarchy>")));
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("<hierarchy><node text=\"last\"/></hierarchy>")));
+        let client = android_client(&transport).await;
+
+        assert!(client.at_scroll_end(By::id("list")).await.unwrap());
+
+        let moves = pointer_moves(&transport);
+        assert_eq!(moves.first(), Some(&(500, 800)));
+        assert_eq!(moves.last(), Some(&(500, 600)));
+        assert_eq!(actions_count(&transport), 1);
+    }
+
+    #[tokio::test]
+    async fn changed_source_means_more_content_and_swipe_is_reversed() {
+        let transport = MockTransport::new();
+        scrollable(&transport);
+        transport.respond(MockResponse::value(json!("<hierarchy><node text=\"1\"/></hierarchy>")));
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("<hierarchy><node text=\"2\"/></hierarchy>")));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        assert!(!client.at_scroll_end(By::id("list")).await.unwrap());
+
+        let moves = pointer_moves(&transport);
+        assert_eq!(moves.first(), Some(&(500, 600)));
+        assert_eq!(moves.last(), Some(&(500, 800)));
+        assert_eq!(actions_count(&transport), 2);
+    }
+
+    #[tokio::test]
+    async fn swipe_by_percent_is_converted_to_pixels() {
+        let transport = MockTransport::new();
+        transport.respond(window_rect());
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.swipe_by_percent((0.5, 0.8), (0.5, 0.2), Duration::from_millis(500)).await.unwrap();
+
+        assert_eq!(pointer_moves(&transport), vec![(540, 1920), (540, 480)]);
+    }
+
+    #[tokio::test]
+    async fn swipe_by_percent_outside_window_is_refused() {
+        let transport = MockTransport::new();
+        transport.respond(window_rect());
+        let client = android_client(&transport).await;
+
+        let result = client.swipe_by_percent((0.5, 1.2), (0.5, 0.2), Duration::from_millis(500)).await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert!(transport.requests_to("/actions").is_empty());
+    }
+
+    #[tokio::test]
+    async fn scroll_until_stops_when_source_matches() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("<hierarchy><node text=\"1\"/></hierarchy>")));
+        transport.respond(window_rect());
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("<hierarchy><node text=\"2\"/></hierarchy>")));
+        transport.respond(window_rect());
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("<hierarchy><node text=\"$ 42\"/></hierarchy>")));
+        let client = android_client(&transport).await;
+
+        let found = client.scroll_until(SwipeDirection::Up, 10, |source| source.contains('$')).await.unwrap();
+
+        assert!(found);
+        assert_eq!(actions_count(&transport), 2);
+    }
+
+    #[tokio::test]
+    async fn scroll_until_gives_up_after_max_swipes() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("<hierarchy/>")));
+        for _ in 0..2 {
+            transport.respond(window_rect());
+            transport.respond(MockResponse::value(json!(null)));
+            transport.respond(MockResponse::value(json!("<hierarchy/>")));
+        }
+        let client = android_client(&transport).await;
+
+        let found = client.scroll_until(SwipeDirection::Up, 2, |source| source.contains('$')).await.unwrap();
+
+        assert!(!found);
+        assert_eq!(actions_count(&transport), 2);
+    }
+
+    #[tokio::test]
+    async fn tap_at_taps_the_point() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.tap_at(200, 300).await.unwrap();
+
+        assert_eq!(pointer_moves(&transport), vec![(200, 300)]);
+        assert_eq!(pointer_action_types(&transport), vec!["pointerMove", "pointerDown", "pointerUp"]);
+    }
+
+    #[tokio::test]
+    async fn double_tap_at_pauses_between_taps() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.double_tap_at(200, 300).await.unwrap();
+
+        assert_eq!(pointer_action_types(&transport), vec!["pointerMove", "pointerDown", "pointerUp", "pause", "pointerDown", "pointerUp"]);
+    }
+
+    #[tokio::test]
+    async fn tap_at_negative_point_is_refused() {
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+
+        let result = client.tap_at(-1, 300).await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert_eq!(actions_count(&transport), 0);
+    }
+
+    #[tokio::test]
+    async fn ease_out_swipe_slows_down() {
+        let transport = MockTransport::new();
+        transport.respond(window_rect());
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.swipe_by_percent_with_profile((0.5, 0.8), (0.5, 0.2), Duration::from_millis(500), VelocityProfile::EaseOut).await.unwrap();
+
+        let moves = pointer_moves(&transport);
+        assert_eq!(moves, vec![(540, 1920), (540, 1402), (540, 998), (540, 710), (540, 538), (540, 480)]);
+        let distances: Vec<_> = moves.windows(2).map(|pair| pair[0].1 - pair[1].1).collect();
+        assert!(distances.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[tokio::test]
+    async fn pause_holds_the_finger_for_duration() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+        let hold = TouchActions::new("finger".to_string())
+            .then(PointerAction::Down { button: MOUSE_BUTTON_LEFT })
+            .then(pause(Duration::from_millis(750)))
+            .then(PointerAction::Up { button: MOUSE_BUTTON_LEFT });
+
+        client.perform_actions(hold).await.unwrap();
+
+        let body = transport.requests().pop().unwrap().body.unwrap();
+        assert_eq!(body["actions"][0]["actions"][1], json!({ "type": "pause", "duration": 750 }));
+    }
+
+    #[tokio::test]
+    async fn long_press_then_wait_for_menu_then_tap() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "x": 0, "y": 400, "width": 1080, "height": 200 })));
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::no_such_element());
+        transport.respond(MockResponse::element("delete-1"));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+        let message = Element::from_element_id((*client).clone(), ElementRef::from("message-1".to_string()));
+
+        client.long_press_then_select(&message, By::accessibility_id("Delete"), Duration::from_secs(1)).await.unwrap();
+
+        let paths: Vec<_> = transport.requests().into_iter().skip(1).map(|request| request.path).collect();
+        assert_eq!(paths, vec![
+            "/session/mock-session/element/message-1/rect",
+            "/session/mock-session/actions",
+            "/session/mock-session/element",
+            "/session/mock-session/element",
+            "/session/mock-session/element/delete-1/click",
+        ]);
+        assert_eq!(pointer_moves(&transport), vec![(540, 500)]);
+        assert_eq!(pointer_action_types(&transport), vec!["pointerMove", "pointerDown", "pause", "pointerUp"]);
+    }
+}
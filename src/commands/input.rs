@@ -0,0 +1,299 @@
+//! Typing text into elements
+use async_trait::async_trait;
+use fantoccini::elements::Element;
+use fantoccini::error::CmdError;
+use http::Method;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::commands::AppiumCommand;
+use crate::commands::android::CanReplaceValue;
+use crate::error::InputError;
+use crate::find::element_from_value;
+
+/// Set text of input fields (cross-platform)
+#[async_trait]
+pub trait SetsValue: AppiumClientTrait {
+    /// Types text into element, optionally clearing it first.
+    ///
+    /// Unlike [crate::commands::android::CanReplaceValue::replace_value], this works on both Android and iOS,
+    /// because it simulates typing (W3C element send keys).
+    async fn set_value(&self, element: &Element, text: &str, clear_first: bool) -> Result<(), CmdError> {
+        if clear_first {
+            element.clear().await?;
+        }
+
+        element.send_keys(text).await
+    }
+
+    /// Clears element, then checks if it's really empty (and retries once if it's not).
+    ///
+    /// `clear` sometimes leaves some text in native fields, so this reads the text back to be sure.
+    /// Drivers return the hint (placeholder) as text of an empty field, so the hint counts as empty.
+    /// Returns [InputError::NotCleared] if the element still has some text after the second attempt.
+    async fn clear_verified(&self, element: &Element) -> Result<(), InputError> {
+        let mut text = String::new();
+        for _ in 0..2 {
+            element.clear().await?;
+
+            text = field_text(element).await?;
+            if text.is_empty() {
+                return Ok(());
+            }
+        }
+
+        Err(InputError::NotCleared {
+            element: element.element_id().to_string(),
+            text,
+        })
+    }
+}
+
+#[async_trait]
+impl SetsValue for AndroidClient {}
+
+#[async_trait]
+impl SetsValue for IOSClient {}
+
+/// Type into password (secure) fields
+#[async_trait]
+pub trait TypesSecure: AppiumClientTrait {
+    /// Types text into a password field (replacing what was there), using a strategy that works on given platform.
+    ///
+    /// * Android - replaces value of the field (see [CanReplaceValue::replace_value]),
+    /// * iOS - clears the field, taps it (to activate the keyboard), then types.
+    ///
+    /// Then it checks if the field has the same number of (masked) characters as `text`,
+    /// or returns [InputError::WrongLength]. A hint (placeholder) of the field counts as no characters.
+    async fn type_secure(&self, element: &Element, text: &str) -> Result<(), InputError>;
+}
+
+#[async_trait]
+impl TypesSecure for AndroidClient {
+    async fn type_secure(&self, element: &Element, text: &str) -> Result<(), InputError> {
+        self.replace_value(element, text).await?;
+        verify_char_count(element, text).await
+    }
+}
+
+#[async_trait]
+impl TypesSecure for IOSClient {
+    async fn type_secure(&self, element: &Element, text: &str) -> Result<(), InputError> {
+        element.clear().await?;
+        element.click().await?;
+        element.send_keys(text).await?;
+        verify_char_count(element, text).await
+    }
+}
+
+/// Text of a text field, or an empty string if the field shows its hint (placeholder).
+///
+/// Drivers return the hint of an empty field as its text (UiAutomator2) or value (XCUITest),
+/// so it's compared with `hint` (Android) and `placeholderValue` (iOS) attributes.
+async fn field_text(element: &Element) -> Result<String, CmdError> {
+    let text = element.text().await?;
+    if text.is_empty() {
+        return Ok(text);
+    }
+
+    for attribute in ["hint", "placeholderValue"] {
+        // each driver knows only one of these attributes, the other one may fail
+        if let Ok(Some(hint)) = element.attr(attribute).await {
+            if hint == text {
+                return Ok(String::new());
+            }
+        }
+    }
+
+    Ok(text)
+}
+
+async fn verify_char_count(element: &Element, expected: &str) -> Result<(), InputError> {
+    let (actual, expected) = (field_text(element).await?.chars().count(), expected.chars().count());
+    if actual != expected {
+        return Err(InputError::WrongLength {
+            element: element.element_id().to_string(),
+            actual,
+            expected,
+        });
+    }
+
+    Ok(())
+}
+
+/// Get the focused element
+#[async_trait]
+pub trait HasActiveElement: AppiumClientTrait {
+    /// Returns the element that currently has focus (e.g. the text field you type into).
+    async fn active_element(&self) -> Result<Element, CmdError> {
+        let value = self.issue_cmd(AppiumCommand::Custom(
+            Method::GET,
+            "element/active".to_string(),
+            None
+        )).await?;
+
+        element_from_value(self.deref().clone(), value)
+    }
+}
+
+#[async_trait]
+impl HasActiveElement for AndroidClient {}
+
+#[async_trait]
+impl HasActiveElement for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use fantoccini::elements::{Element, ElementRef};
+    use http::Method;
+    use serde_json::json;
+    use crate::commands::input::{HasActiveElement, SetsValue, TypesSecure};
+    use crate::error::InputError;
+    use crate::mock::{android_client, ios_client, MockResponse, MockTransport, RecordedRequest};
+
+    fn element(client: &fantoccini::Client) -> Element {
+        Element::from_element_id(client.clone(), ElementRef::from("field-1".to_string()))
+    }
+
+    fn commands(transport: &MockTransport) -> Vec<RecordedRequest> {
+        // without new session and capabilities of the session
+        transport.requests().into_iter().skip(1).collect()
+    }
+
+    fn clears(transport: &MockTransport) -> usize {
+        transport.requests_to("/clear").iter()
+            .filter(|request| request.method == Method::POST)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn set_value_clears_field_first_if_asked() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.set_value(&element(&client), "hello", true).await.unwrap();
+
+        let commands = commands(&transport);
+        assert_eq!(commands[0].path, "/session/mock-session/element/field-1/clear");
+        assert_eq!(commands[1].path, "/session/mock-session/element/field-1/value");
+        assert_eq!(commands[1].body.as_ref().unwrap()["text"], "hello");
+    }
+
+    #[tokio::test]
+    async fn set_value_types_without_clearing() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.set_value(&element(&client), "hello", false).await.unwrap();
+
+        assert_eq!(clears(&transport), 0);
+        assert_eq!(commands(&transport).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn hint_of_cleared_field_counts_as_empty() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("Email")));
+        transport.respond(MockResponse::value(json!("Email")));
+        let client = android_client(&transport).await;
+
+        client.clear_verified(&element(&client)).await.unwrap();
+
+        assert_eq!(clears(&transport), 1);
+        assert!(transport.requests().last().unwrap().path.ends_with("/element/field-1/attribute/hint"));
+    }
+
+    #[tokio::test]
+    async fn text_left_after_second_clear_is_an_error() {
+        let transport = MockTransport::new();
+        for _ in 0..2 {
+            transport.respond(MockResponse::value(json!(null)));
+            transport.respond(MockResponse::value(json!("left")));
+            transport.respond(MockResponse::value(json!("Email")));
+            transport.respond(MockResponse::value(json!(null)));
+        }
+        let client = android_client(&transport).await;
+
+        let result = client.clear_verified(&element(&client)).await;
+
+        assert!(matches!(result, Err(InputError::NotCleared { text, .. }) if text == "left"));
+        assert_eq!(clears(&transport), 2);
+    }
+
+    #[tokio::test]
+    async fn type_secure_replaces_value_on_android() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("••••••")));
+        let client = android_client(&transport).await;
+
+        client.type_secure(&element(&client), "secret").await.unwrap();
+
+        let commands = commands(&transport);
+        assert_eq!(commands[0].path, "/session/mock-session/appium/element/field-1/replace_value");
+        assert!(commands[1].path.ends_with("/element/field-1/text"));
+    }
+
+    #[tokio::test]
+    async fn type_secure_clears_taps_and_types_on_ios() {
+        let transport = MockTransport::new();
+        for _ in 0..3 {
+            transport.respond(MockResponse::value(json!(null)));
+        }
+        transport.respond(MockResponse::value(json!("••••••")));
+        let client = ios_client(&transport).await;
+
+        client.type_secure(&element(&client), "secret").await.unwrap();
+
+        let paths: Vec<_> = commands(&transport).into_iter().map(|request| request.path).collect();
+        assert_eq!(paths[..4], [
+            "/session/mock-session/element/field-1/clear",
+            "/session/mock-session/element/field-1/click",
+            "/session/mock-session/element/field-1/value",
+            "/session/mock-session/element/field-1/text",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn placeholder_left_after_typing_is_wrong_length() {
+        let transport = MockTransport::new();
+        for _ in 0..3 {
+            transport.respond(MockResponse::value(json!(null)));
+        }
+        transport.respond(MockResponse::value(json!("Password")));
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("Password")));
+        let client = ios_client(&transport).await;
+
+        let result = client.type_secure(&element(&client), "secret").await;
+
+        assert!(matches!(result, Err(InputError::WrongLength { actual: 0, expected: 6, .. })));
+    }
+
+    #[tokio::test]
+    async fn active_element_is_parsed_from_w3c_key() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::element("field-2"));
+        let client = android_client(&transport).await;
+
+        let active = client.active_element().await.unwrap();
+
+        assert_eq!(active.element_id().as_ref(), "field-2");
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.method, Method::GET);
+        assert_eq!(request.path, "/session/mock-session/element/active");
+    }
+
+    #[tokio::test]
+    async fn active_element_is_parsed_from_legacy_key() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "ELEMENT": "field-3" })));
+        let client = ios_client(&transport).await;
+
+        let active = client.active_element().await.unwrap();
+
+        assert_eq!(active.element_id().as_ref(), "field-3");
+    }
+}
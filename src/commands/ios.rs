@@ -1,9 +1,12 @@
 //! iOS-specific features
 use async_trait::async_trait;
+use fantoccini::elements::Element;
 use fantoccini::error::CmdError;
 use http::Method;
+use serde_derive::Serialize;
+use serde_json::{json, Map, Value};
 use crate::{AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 /// Simulate device shake
 #[async_trait]
@@ -20,4 +23,148 @@ pub trait ShakesDevice : AppiumClientTrait {
     }
 }
 
-impl ShakesDevice for IOSClient {}
\ No newline at end of file
+impl ShakesDevice for IOSClient {}
+
+/// Format of page source returned by [HasSourceFormats::source_with_format]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Xml,
+    Json,
+    Description,
+}
+
+/// Get page source in other formats than XML (XCUITest only)
+#[async_trait]
+pub trait HasSourceFormats : AppiumClientTrait {
+    /// Page source in given format, via `mobile: source`.
+    ///
+    /// JSON is returned as an object, other formats as a string.
+    async fn source_with_format(&self, format: SourceFormat) -> Result<Value, CmdError> {
+        self.execute("mobile: source", vec![json!({
+            "format": format
+        })]).await
+    }
+
+    /// Page source (accessibility tree) as JSON, which is easier to inspect than XML from `source()`.
+    async fn source_json(&self) -> Result<Value, CmdError> {
+        let value = match self.source_with_format(SourceFormat::Json).await? {
+            // some versions of the driver return JSON as a string
+            Value::String(json) => serde_json::from_str(&json).unwrap_or(Value::String(json)),
+            value => value,
+        };
+
+        parse_response::<Map<String, Value>>("mobile: source", value)
+            .map(Value::Object)
+    }
+}
+
+#[async_trait]
+impl HasSourceFormats for IOSClient {}
+
+/// Set values of picker wheels (XCUITest only)
+#[async_trait]
+pub trait SetsPickerValue : AppiumClientTrait {
+    /// Sets values of picker wheels, one value per wheel (in given order).
+    ///
+    /// Every column of a picker is a separate `XCUIElementTypePickerWheel` element,
+    /// so a multi-column picker (e.g. a date picker) is set with one request per wheel.
+    ///
+    /// ```no_run
+    /// # use appium_client::IOSClient;
+    /// # use appium_client::commands::ios::SetsPickerValue;
+    /// # use appium_client::find::{AppiumFind, By};
+    /// # async fn pick(client: &IOSClient) -> Result<(), fantoccini::error::CmdError> {
+    /// let wheels = client.find_all_by(By::class_name("XCUIElementTypePickerWheel")).await?;
+    /// client.set_picker_value(&[(&wheels[0], "March"), (&wheels[1], "14")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn set_picker_value(&self, wheels: &[(&Element, &str)]) -> Result<(), CmdError> {
+        for (wheel, value) in wheels {
+            self.issue_cmd(AppiumCommand::Custom(
+                Method::POST,
+                format!("element/{}/value", wheel.element_id()),
+                Some(json!({
+                    "value": [value],
+                    "text": value
+                }))
+            )).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SetsPickerValue for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use fantoccini::elements::{Element, ElementRef};
+    use fantoccini::error::CmdError;
+    use serde_json::json;
+    use crate::commands::ios::{HasSourceFormats, SetsPickerValue, SourceFormat};
+    use crate::mock::{ios_client, MockResponse, MockTransport};
+
+    #[tokio::test]
+    async fn source_format_is_sent_as_argument() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("XCUIElementTypeApplication, 0x600, {{0.0, 0.0}, {390.0, 844.0}}")));
+        let client = ios_client(&transport).await;
+
+        client.source_with_format(SourceFormat::Description).await.unwrap();
+
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/execute/sync");
+        assert_eq!(request.body.unwrap(), json!({
+            "script": "mobile: source",
+            "args": [{ "format": "description" }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn json_source_returned_as_string_is_parsed() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(r#"{"type": "XCUIElementTypeApplication", "children": []}"#)));
+        let client = ios_client(&transport).await;
+
+        let source = client.source_json().await.unwrap();
+
+        assert_eq!(source, json!({ "type": "XCUIElementTypeApplication", "children": [] }));
+        let body = transport.requests().pop().unwrap().body.unwrap();
+        assert_eq!(body["args"], json!([{ "format": "json" }]));
+    }
+
+    #[tokio::test]
+    async fn invalid_json_source_error_names_command() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("<AppiumAUT>")));
+        let client = ios_client(&transport).await;
+
+        let error = client.source_json().await.unwrap_err();
+
+        assert!(matches!(error, CmdError::Json(_)));
+        assert!(error.to_string().contains("mobile: source"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn picker_value_is_set_on_every_wheel() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+        let wheel = |id: &str| Element::from_element_id((*client).clone(), ElementRef::from(id.to_string()));
+        let (month, day) = (wheel("wheel-1"), wheel("wheel-2"));
+
+        client.set_picker_value(&[(&month, "March"), (&day, "14")]).await.unwrap();
+
+        let requests: Vec<_> = transport.requests_to("/value").into_iter()
+            .map(|request| (request.path, request.body.unwrap()))
+            .collect();
+        assert_eq!(requests, vec![
+            ("/session/mock-session/element/wheel-1/value".to_string(), json!({ "value": ["March"], "text": "March" })),
+            ("/session/mock-session/element/wheel-2/value".to_string(), json!({ "value": ["14"], "text": "14" })),
+        ]);
+    }
+}
@@ -1,12 +1,18 @@
 //! Keyboard management
+//!
+//! The `mobile_` methods send `mobile:` commands instead of requests to legacy `appium/device/...` endpoints,
+//! which some newer drivers no longer have.
+use std::time::Duration;
 use async_trait::async_trait;
 use fantoccini::error::CmdError;
 use http::Method;
 use serde_derive::{Serialize, Deserialize};
 use serde_json::json;
 use serde_repr::Serialize_repr;
+use tokio::time::sleep;
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
+use crate::commands::android::StartsActivity;
 
 /// Hide onscreen keyboard
 #[async_trait]
@@ -50,6 +56,12 @@ pub trait HidesKeyboard: AppiumClientTrait {
         )).await?;
         Ok(())
     }
+
+    /// [HidesKeyboard::hide_keyboard] with `mobile: hideKeyboard`.
+    async fn mobile_hide_keyboard(&self) -> Result<(), CmdError> {
+        self.execute("mobile: hideKeyboard", vec![json!({})]).await?;
+        Ok(())
+    }
 }
 
 
@@ -153,11 +165,110 @@ pub trait PressesKey: AppiumClientTrait {
 
         Ok(())
     }
+
+    /// Opens recent apps (overview), e.g. to test multitasking.
+    ///
+    /// It's a press of [AndroidKey::AppSwitch] (`KEYCODE_APP_SWITCH`).
+    /// Pressing it again goes back to the previous app.
+    async fn open_recents(&self) -> Result<(), CmdError> {
+        self.press_key(AndroidKey::AppSwitch.into()).await
+    }
+
+    /// Performs steps (key presses and delays) one by one, e.g. to navigate a TV UI with a "remote".
+    ///
+    /// ```no_run
+    ///# use std::time::Duration;
+    ///# use appium_client::AndroidClient;
+    ///# use appium_client::commands::keyboard::{AndroidKey, KeyMacroStep, PressesKey};
+    ///# async fn navigate(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// client.press_key_macro(&[
+    ///     KeyMacroStep::Press(AndroidKey::DPadDown.into()),
+    ///     KeyMacroStep::Delay(Duration::from_millis(300)),
+    ///     KeyMacroStep::Press(AndroidKey::DPadDown.into()),
+    ///     KeyMacroStep::Delay(Duration::from_millis(300)),
+    ///     KeyMacroStep::Press(AndroidKey::DPadCenter.into()),
+    /// ]).await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    async fn press_key_macro(&self, steps: &[KeyMacroStep]) -> Result<(), CmdError> {
+        for step in steps {
+            match step {
+                KeyMacroStep::Press(event) => self.press_key(event.clone()).await?,
+                KeyMacroStep::LongPress(event) => self.long_press_key(event.clone()).await?,
+                KeyMacroStep::Delay(duration) => sleep(*duration).await,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Step of a key macro (see [PressesKey::press_key_macro])
+#[derive(Debug, Clone)]
+pub enum KeyMacroStep {
+    Press(KeyEvent),
+    LongPress(KeyEvent),
+    Delay(Duration),
 }
 
 #[async_trait]
 impl PressesKey for AndroidClient {}
 
+/// Press hardware Back button and check what happened
+#[async_trait]
+pub trait PressesBack: PressesKey + StartsActivity {
+    /// Presses Back and returns whether the app backed out (the current package changed).
+    ///
+    /// Appium does not report if a key press was handled, so this compares the current package before and after pressing Back.
+    /// Returns `false` if the app consumed the press (e.g. closed a dialog or navigated inside the app).
+    ///
+    /// Note: the check is done right after the key press, so slow transitions might not be detected yet.
+    async fn press_back_and_detect_exit(&self) -> Result<bool, CmdError> {
+        let before = self.current_package().await?;
+        self.press_key(AndroidKey::Back.into()).await?;
+        let after = self.current_package().await?;
+
+        Ok(before != after)
+    }
+}
+
+#[async_trait]
+impl PressesBack for AndroidClient {}
+
+/// IME action of a text field (the action button of onscreen keyboard)
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum EditorAction {
+    Normal,
+    Unspecified,
+    None,
+    Go,
+    Search,
+    Send,
+    Next,
+    Done,
+    Previous,
+}
+
+/// Perform IME actions (Android)
+#[async_trait]
+pub trait PerformsEditorAction: AppiumClientTrait {
+    /// Performs IME action on the focused field (e.g. "Search" or "Done" button of onscreen keyboard).
+    ///
+    /// This is not the same as pressing Enter, because apps usually listen to editor actions, not to key presses.
+    async fn perform_editor_action(&self, action: EditorAction) -> Result<(), CmdError> {
+        self.execute("mobile: performEditorAction", vec![json!({
+            "action": action
+        })]).await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PerformsEditorAction for AndroidClient {}
+
 /// Check onscreen keyboard
 #[async_trait]
 pub trait HasOnScreenKeyboard: AppiumClientTrait {
@@ -170,7 +281,13 @@ pub trait HasOnScreenKeyboard: AppiumClientTrait {
             )
         ).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/is_keyboard_shown", value)
+    }
+
+    /// [HasOnScreenKeyboard::keyboard_shown] with `mobile: isKeyboardShown`.
+    async fn mobile_keyboard_shown(&self) -> Result<bool, CmdError> {
+        let value = self.execute("mobile: isKeyboardShown", vec![json!({})]).await?;
+        parse_response("mobile: isKeyboardShown", value)
     }
 }
 
@@ -1182,6 +1299,594 @@ pub enum AndroidKey {
     Paste = 279,
 }
 
+impl AndroidKey {
+    /// Name of the key code, as in Android `KeyEvent` (e.g. `KEYCODE_BACK`).
+    ///
+    /// ```
+    /// use appium_client::commands::keyboard::AndroidKey;
+    ///
+    /// assert_eq!(AndroidKey::DPadUp.as_name(), "KEYCODE_DPAD_UP");
+    /// assert_eq!(AndroidKey::from_name("KEYCODE_DPAD_UP"), Some(AndroidKey::DPadUp));
+    /// assert_eq!(AndroidKey::from_name("ENTER"), Some(AndroidKey::Enter));
+    /// assert_eq!(AndroidKey::from_name("KEYCODE_NOT_A_KEY"), None);
+    /// ```
+    pub fn as_name(&self) -> &'static str {
+        match self {
+            AndroidKey::Unknown => "KEYCODE_UNKNOWN",
+            AndroidKey::SoftLeft => "KEYCODE_SOFT_LEFT",
+            AndroidKey::SoftRight => "KEYCODE_SOFT_RIGHT",
+            AndroidKey::Home => "KEYCODE_HOME",
+            AndroidKey::Back => "KEYCODE_BACK",
+            AndroidKey::Call => "KEYCODE_CALL",
+            AndroidKey::EndCall => "KEYCODE_ENDCALL",
+            AndroidKey::Digit0 => "KEYCODE_0",
+            AndroidKey::Digit1 => "KEYCODE_1",
+            AndroidKey::Digit2 => "KEYCODE_2",
+            AndroidKey::Digit3 => "KEYCODE_3",
+            AndroidKey::Digit4 => "KEYCODE_4",
+            AndroidKey::Digit5 => "KEYCODE_5",
+            AndroidKey::Digit6 => "KEYCODE_6",
+            AndroidKey::Digit7 => "KEYCODE_7",
+            AndroidKey::Digit8 => "KEYCODE_8",
+            AndroidKey::Digit9 => "KEYCODE_9",
+            AndroidKey::Asterisk => "KEYCODE_STAR",
+            AndroidKey::Pound => "KEYCODE_POUND",
+            AndroidKey::DPadUp => "KEYCODE_DPAD_UP",
+            AndroidKey::DPadDown => "KEYCODE_DPAD_DOWN",
+            AndroidKey::DPadLeft => "KEYCODE_DPAD_LEFT",
+            AndroidKey::DpadRight => "KEYCODE_DPAD_RIGHT",
+            AndroidKey::DPadCenter => "KEYCODE_DPAD_CENTER",
+            AndroidKey::VolumeUp => "KEYCODE_VOLUME_UP",
+            AndroidKey::VolumeDown => "KEYCODE_VOLUME_DOWN",
+            AndroidKey::Power => "KEYCODE_POWER",
+            AndroidKey::Camera => "KEYCODE_CAMERA",
+            AndroidKey::Clear => "KEYCODE_CLEAR",
+            AndroidKey::A => "KEYCODE_A",
+            AndroidKey::B => "KEYCODE_B",
+            AndroidKey::C => "KEYCODE_C",
+            AndroidKey::D => "KEYCODE_D",
+            AndroidKey::E => "KEYCODE_E",
+            AndroidKey::F => "KEYCODE_F",
+            AndroidKey::G => "KEYCODE_G",
+            AndroidKey::H => "KEYCODE_H",
+            AndroidKey::I => "KEYCODE_I",
+            AndroidKey::J => "KEYCODE_J",
+            AndroidKey::K => "KEYCODE_K",
+            AndroidKey::L => "KEYCODE_L",
+            AndroidKey::M => "KEYCODE_M",
+            AndroidKey::N => "KEYCODE_N",
+            AndroidKey::O => "KEYCODE_O",
+            AndroidKey::P => "KEYCODE_P",
+            AndroidKey::Q => "KEYCODE_Q",
+            AndroidKey::R => "KEYCODE_R",
+            AndroidKey::S => "KEYCODE_S",
+            AndroidKey::T => "KEYCODE_T",
+            AndroidKey::U => "KEYCODE_U",
+            AndroidKey::V => "KEYCODE_V",
+            AndroidKey::W => "KEYCODE_W",
+            AndroidKey::X => "KEYCODE_X",
+            AndroidKey::Y => "KEYCODE_Y",
+            AndroidKey::Z => "KEYCODE_Z",
+            AndroidKey::Comma => "KEYCODE_COMMA",
+            AndroidKey::Period => "KEYCODE_PERIOD",
+            AndroidKey::AltLeft => "KEYCODE_ALT_LEFT",
+            AndroidKey::AltRight => "KEYCODE_ALT_RIGHT",
+            AndroidKey::ShiftLeft => "KEYCODE_SHIFT_LEFT",
+            AndroidKey::ShiftRight => "KEYCODE_SHIFT_RIGHT",
+            AndroidKey::Tab => "KEYCODE_TAB",
+            AndroidKey::Space => "KEYCODE_SPACE",
+            AndroidKey::SYM => "KEYCODE_SYM",
+            AndroidKey::Explorer => "KEYCODE_EXPLORER",
+            AndroidKey::Envelope => "KEYCODE_ENVELOPE",
+            AndroidKey::Enter => "KEYCODE_ENTER",
+            AndroidKey::DEL => "KEYCODE_DEL",
+            AndroidKey::Grave => "KEYCODE_GRAVE",
+            AndroidKey::Minus => "KEYCODE_MINUS",
+            AndroidKey::Equals => "KEYCODE_EQUALS",
+            AndroidKey::LeftBracket => "KEYCODE_LEFT_BRACKET",
+            AndroidKey::RightBracket => "KEYCODE_RIGHT_BRACKET",
+            AndroidKey::Backslash => "KEYCODE_BACKSLASH",
+            AndroidKey::Semicolon => "KEYCODE_SEMICOLON",
+            AndroidKey::Apostrophe => "KEYCODE_APOSTROPHE",
+            AndroidKey::Slash => "KEYCODE_SLASH",
+            AndroidKey::At => "KEYCODE_AT",
+            AndroidKey::NUM => "KEYCODE_NUM",
+            AndroidKey::HeadsetHook => "KEYCODE_HEADSETHOOK",
+            AndroidKey::Focus => "KEYCODE_FOCUS",
+            AndroidKey::Plus => "KEYCODE_PLUS",
+            AndroidKey::Menu => "KEYCODE_MENU",
+            AndroidKey::Notification => "KEYCODE_NOTIFICATION",
+            AndroidKey::Search => "KEYCODE_SEARCH",
+            AndroidKey::MediaPlayPause => "KEYCODE_MEDIA_PLAY_PAUSE",
+            AndroidKey::MediaStop => "KEYCODE_MEDIA_STOP",
+            AndroidKey::MediaNext => "KEYCODE_MEDIA_NEXT",
+            AndroidKey::MediaPrevious => "KEYCODE_MEDIA_PREVIOUS",
+            AndroidKey::MediaRewind => "KEYCODE_MEDIA_REWIND",
+            AndroidKey::MediaFastForward => "KEYCODE_MEDIA_FAST_FORWARD",
+            AndroidKey::Mute => "KEYCODE_MUTE",
+            AndroidKey::PageUp => "KEYCODE_PAGE_UP",
+            AndroidKey::PageDown => "KEYCODE_PAGE_DOWN",
+            AndroidKey::PictSymbols => "KEYCODE_PICTSYMBOLS",
+            AndroidKey::SwitchCharset => "KEYCODE_SWITCH_CHARSET",
+            AndroidKey::ButtonA => "KEYCODE_BUTTON_A",
+            AndroidKey::ButtonB => "KEYCODE_BUTTON_B",
+            AndroidKey::ButtonC => "KEYCODE_BUTTON_C",
+            AndroidKey::ButtonX => "KEYCODE_BUTTON_X",
+            AndroidKey::ButtonY => "KEYCODE_BUTTON_Y",
+            AndroidKey::ButtonZ => "KEYCODE_BUTTON_Z",
+            AndroidKey::ButtonL1 => "KEYCODE_BUTTON_L1",
+            AndroidKey::ButtonR1 => "KEYCODE_BUTTON_R1",
+            AndroidKey::ButtonL2 => "KEYCODE_BUTTON_L2",
+            AndroidKey::ButtonR2 => "KEYCODE_BUTTON_R2",
+            AndroidKey::ButtonThumbL => "KEYCODE_BUTTON_THUMBL",
+            AndroidKey::ButtonThumbR => "KEYCODE_BUTTON_THUMBR",
+            AndroidKey::ButtonStart => "KEYCODE_BUTTON_START",
+            AndroidKey::ButtonSelect => "KEYCODE_BUTTON_SELECT",
+            AndroidKey::ButtonMode => "KEYCODE_BUTTON_MODE",
+            AndroidKey::Escape => "KEYCODE_ESCAPE",
+            AndroidKey::ForwardDel => "KEYCODE_FORWARD_DEL",
+            AndroidKey::CtrlLeft => "KEYCODE_CTRL_LEFT",
+            AndroidKey::CtrlRight => "KEYCODE_CTRL_RIGHT",
+            AndroidKey::CapsLock => "KEYCODE_CAPS_LOCK",
+            AndroidKey::ScrollLock => "KEYCODE_SCROLL_LOCK",
+            AndroidKey::MetaLeft => "KEYCODE_META_LEFT",
+            AndroidKey::MetaRight => "KEYCODE_META_RIGHT",
+            AndroidKey::Function => "KEYCODE_FUNCTION",
+            AndroidKey::SYSRQ => "KEYCODE_SYSRQ",
+            AndroidKey::Break => "KEYCODE_BREAK",
+            AndroidKey::MoveHome => "KEYCODE_MOVE_HOME",
+            AndroidKey::MoveEnd => "KEYCODE_MOVE_END",
+            AndroidKey::Insert => "KEYCODE_INSERT",
+            AndroidKey::Forward => "KEYCODE_FORWARD",
+            AndroidKey::MediaPlay => "KEYCODE_MEDIA_PLAY",
+            AndroidKey::MediaPause => "KEYCODE_MEDIA_PAUSE",
+            AndroidKey::MediaClose => "KEYCODE_MEDIA_CLOSE",
+            AndroidKey::MediaEject => "KEYCODE_MEDIA_EJECT",
+            AndroidKey::MediaRecord => "KEYCODE_MEDIA_RECORD",
+            AndroidKey::F1 => "KEYCODE_F1",
+            AndroidKey::F2 => "KEYCODE_F2",
+            AndroidKey::F3 => "KEYCODE_F3",
+            AndroidKey::F4 => "KEYCODE_F4",
+            AndroidKey::F5 => "KEYCODE_F5",
+            AndroidKey::F6 => "KEYCODE_F6",
+            AndroidKey::F7 => "KEYCODE_F7",
+            AndroidKey::F8 => "KEYCODE_F8",
+            AndroidKey::F9 => "KEYCODE_F9",
+            AndroidKey::F10 => "KEYCODE_F10",
+            AndroidKey::F11 => "KEYCODE_F11",
+            AndroidKey::F12 => "KEYCODE_F12",
+            AndroidKey::NumLock => "KEYCODE_NUM_LOCK",
+            AndroidKey::Numpad0 => "KEYCODE_NUMPAD_0",
+            AndroidKey::Numpad1 => "KEYCODE_NUMPAD_1",
+            AndroidKey::Numpad2 => "KEYCODE_NUMPAD_2",
+            AndroidKey::Numpad3 => "KEYCODE_NUMPAD_3",
+            AndroidKey::Numpad4 => "KEYCODE_NUMPAD_4",
+            AndroidKey::Numpad5 => "KEYCODE_NUMPAD_5",
+            AndroidKey::Numpad6 => "KEYCODE_NUMPAD_6",
+            AndroidKey::Numpad7 => "KEYCODE_NUMPAD_7",
+            AndroidKey::Numpad8 => "KEYCODE_NUMPAD_8",
+            AndroidKey::Numpad9 => "KEYCODE_NUMPAD_9",
+            AndroidKey::NumpadDivide => "KEYCODE_NUMPAD_DIVIDE",
+            AndroidKey::NumpadMultiply => "KEYCODE_NUMPAD_MULTIPLY",
+            AndroidKey::NumpadSubtract => "KEYCODE_NUMPAD_SUBTRACT",
+            AndroidKey::NumpadAdd => "KEYCODE_NUMPAD_ADD",
+            AndroidKey::NumpadDot => "KEYCODE_NUMPAD_DOT",
+            AndroidKey::NumpadComma => "KEYCODE_NUMPAD_COMMA",
+            AndroidKey::NumpadEnter => "KEYCODE_NUMPAD_ENTER",
+            AndroidKey::NumpadEquals => "KEYCODE_NUMPAD_EQUALS",
+            AndroidKey::NumpadLeftParen => "KEYCODE_NUMPAD_LEFT_PAREN",
+            AndroidKey::NumpadRightParen => "KEYCODE_NUMPAD_RIGHT_PAREN",
+            AndroidKey::VolumeMute => "KEYCODE_VOLUME_MUTE",
+            AndroidKey::Info => "KEYCODE_INFO",
+            AndroidKey::ChannelUp => "KEYCODE_CHANNEL_UP",
+            AndroidKey::ChannelDown => "KEYCODE_CHANNEL_DOWN",
+            AndroidKey::KeycodeZoomIn => "KEYCODE_ZOOM_IN",
+            AndroidKey::KeycodeZoomOut => "KEYCODE_ZOOM_OUT",
+            AndroidKey::TV => "KEYCODE_TV",
+            AndroidKey::Window => "KEYCODE_WINDOW",
+            AndroidKey::Guide => "KEYCODE_GUIDE",
+            AndroidKey::DVR => "KEYCODE_DVR",
+            AndroidKey::Bookmark => "KEYCODE_BOOKMARK",
+            AndroidKey::Captions => "KEYCODE_CAPTIONS",
+            AndroidKey::Settings => "KEYCODE_SETTINGS",
+            AndroidKey::TVPower => "KEYCODE_TV_POWER",
+            AndroidKey::TVInput => "KEYCODE_TV_INPUT",
+            AndroidKey::STBPower => "KEYCODE_STB_POWER",
+            AndroidKey::STBInput => "KEYCODE_STB_INPUT",
+            AndroidKey::AVRPower => "KEYCODE_AVR_POWER",
+            AndroidKey::AVRInput => "KEYCODE_AVR_INPUT",
+            AndroidKey::ProgRed => "KEYCODE_PROG_RED",
+            AndroidKey::ProgGreen => "KEYCODE_PROG_GREEN",
+            AndroidKey::ProgYellow => "KEYCODE_PROG_YELLOW",
+            AndroidKey::ProgBlue => "KEYCODE_PROG_BLUE",
+            AndroidKey::AppSwitch => "KEYCODE_APP_SWITCH",
+            AndroidKey::Button1 => "KEYCODE_BUTTON_1",
+            AndroidKey::Button2 => "KEYCODE_BUTTON_2",
+            AndroidKey::Button3 => "KEYCODE_BUTTON_3",
+            AndroidKey::Button4 => "KEYCODE_BUTTON_4",
+            AndroidKey::Button5 => "KEYCODE_BUTTON_5",
+            AndroidKey::Button6 => "KEYCODE_BUTTON_6",
+            AndroidKey::Button7 => "KEYCODE_BUTTON_7",
+            AndroidKey::Button8 => "KEYCODE_BUTTON_8",
+            AndroidKey::Button9 => "KEYCODE_BUTTON_9",
+            AndroidKey::Button10 => "KEYCODE_BUTTON_10",
+            AndroidKey::Button11 => "KEYCODE_BUTTON_11",
+            AndroidKey::Button12 => "KEYCODE_BUTTON_12",
+            AndroidKey::Button13 => "KEYCODE_BUTTON_13",
+            AndroidKey::Button14 => "KEYCODE_BUTTON_14",
+            AndroidKey::Button15 => "KEYCODE_BUTTON_15",
+            AndroidKey::Button16 => "KEYCODE_BUTTON_16",
+            AndroidKey::LanguageSwitch => "KEYCODE_LANGUAGE_SWITCH",
+            AndroidKey::MannerMode => "KEYCODE_MANNER_MODE",
+            AndroidKey::Mode3D => "KEYCODE_3D_MODE",
+            AndroidKey::Contacts => "KEYCODE_CONTACTS",
+            AndroidKey::Calendar => "KEYCODE_CALENDAR",
+            AndroidKey::Music => "KEYCODE_MUSIC",
+            AndroidKey::Calculator => "KEYCODE_CALCULATOR",
+            AndroidKey::ZenkakuHankaku => "KEYCODE_ZENKAKU_HANKAKU",
+            AndroidKey::Eisu => "KEYCODE_EISU",
+            AndroidKey::Muhenkan => "KEYCODE_MUHENKAN",
+            AndroidKey::Henkan => "KEYCODE_HENKAN",
+            AndroidKey::KatakanaHiragana => "KEYCODE_KATAKANA_HIRAGANA",
+            AndroidKey::Yen => "KEYCODE_YEN",
+            AndroidKey::Ro => "KEYCODE_RO",
+            AndroidKey::Kana => "KEYCODE_KANA",
+            AndroidKey::Assist => "KEYCODE_ASSIST",
+            AndroidKey::BrightnessDown => "KEYCODE_BRIGHTNESS_DOWN",
+            AndroidKey::BrightnessUp => "KEYCODE_BRIGHTNESS_UP",
+            AndroidKey::MediaAudioTrack => "KEYCODE_MEDIA_AUDIO_TRACK",
+            AndroidKey::Sleep => "KEYCODE_SLEEP",
+            AndroidKey::WakeUp => "KEYCODE_WAKEUP",
+            AndroidKey::Pairing => "KEYCODE_PAIRING",
+            AndroidKey::MediaTopMenu => "KEYCODE_MEDIA_TOP_MENU",
+            AndroidKey::Key11 => "KEYCODE_11",
+            AndroidKey::Key12 => "KEYCODE_12",
+            AndroidKey::LastChannel => "KEYCODE_LAST_CHANNEL",
+            AndroidKey::TVDataService => "KEYCODE_TV_DATA_SERVICE",
+            AndroidKey::VoiceAssist => "KEYCODE_VOICE_ASSIST",
+            AndroidKey::TVRadioService => "KEYCODE_TV_RADIO_SERVICE",
+            AndroidKey::TVTeletext => "KEYCODE_TV_TELETEXT",
+            AndroidKey::TVNumberEntry => "KEYCODE_TV_NUMBER_ENTRY",
+            AndroidKey::TVTerrestrialAnalog => "KEYCODE_TV_TERRESTRIAL_ANALOG",
+            AndroidKey::TVTerrestrialDigital => "KEYCODE_TV_TERRESTRIAL_DIGITAL",
+            AndroidKey::TVSatellite => "KEYCODE_TV_SATELLITE",
+            AndroidKey::TVSatelliteBS => "KEYCODE_TV_SATELLITE_BS",
+            AndroidKey::TVSatelliteCS => "KEYCODE_TV_SATELLITE_CS",
+            AndroidKey::TVSatelliteService => "KEYCODE_TV_SATELLITE_SERVICE",
+            AndroidKey::TVNetwork => "KEYCODE_TV_NETWORK",
+            AndroidKey::TVAntennaCable => "KEYCODE_TV_ANTENNA_CABLE",
+            AndroidKey::TVInputHdmi1 => "KEYCODE_TV_INPUT_HDMI_1",
+            AndroidKey::TVInputHdmi2 => "KEYCODE_TV_INPUT_HDMI_2",
+            AndroidKey::TVInputHdmi3 => "KEYCODE_TV_INPUT_HDMI_3",
+            AndroidKey::TVInputHdmi4 => "KEYCODE_TV_INPUT_HDMI_4",
+            AndroidKey::TVInputComposite1 => "KEYCODE_TV_INPUT_COMPOSITE_1",
+            AndroidKey::TVInputComposite2 => "KEYCODE_TV_INPUT_COMPOSITE_2",
+            AndroidKey::TVInputComponent1 => "KEYCODE_TV_INPUT_COMPONENT_1",
+            AndroidKey::TVInputComponent2 => "KEYCODE_TV_INPUT_COMPONENT_2",
+            AndroidKey::TVInputVga1 => "KEYCODE_TV_INPUT_VGA_1",
+            AndroidKey::TVAudioDescription => "KEYCODE_TV_AUDIO_DESCRIPTION",
+            AndroidKey::TVAudioDescriptionMixUp => "KEYCODE_TV_AUDIO_DESCRIPTION_MIX_UP",
+            AndroidKey::TVAudioDescriptionMixDown => "KEYCODE_TV_AUDIO_DESCRIPTION_MIX_DOWN",
+            AndroidKey::TVZoomMode => "KEYCODE_TV_ZOOM_MODE",
+            AndroidKey::TVContentsMenu => "KEYCODE_TV_CONTENTS_MENU",
+            AndroidKey::TVMediaContextMenu => "KEYCODE_TV_MEDIA_CONTEXT_MENU",
+            AndroidKey::TVTimerProgramming => "KEYCODE_TV_TIMER_PROGRAMMING",
+            AndroidKey::Help => "KEYCODE_HELP",
+            AndroidKey::NavigatePrevious => "KEYCODE_NAVIGATE_PREVIOUS",
+            AndroidKey::NavigateNext => "KEYCODE_NAVIGATE_NEXT",
+            AndroidKey::NavigateIn => "KEYCODE_NAVIGATE_IN",
+            AndroidKey::NavigateOut => "KEYCODE_NAVIGATE_OUT",
+            AndroidKey::StemPrimary => "KEYCODE_STEM_PRIMARY",
+            AndroidKey::Stem1 => "KEYCODE_STEM_1",
+            AndroidKey::Stem2 => "KEYCODE_STEM_2",
+            AndroidKey::Stem3 => "KEYCODE_STEM_3",
+            AndroidKey::DpadUpLeft => "KEYCODE_DPAD_UP_LEFT",
+            AndroidKey::DpadDownLeft => "KEYCODE_DPAD_DOWN_LEFT",
+            AndroidKey::DpadUpRight => "KEYCODE_DPAD_UP_RIGHT",
+            AndroidKey::DpadDownRight => "KEYCODE_DPAD_DOWN_RIGHT",
+            AndroidKey::MediaSkipForward => "KEYCODE_MEDIA_SKIP_FORWARD",
+            AndroidKey::MediaSkipBackward => "KEYCODE_MEDIA_SKIP_BACKWARD",
+            AndroidKey::MediaStepForward => "KEYCODE_MEDIA_STEP_FORWARD",
+            AndroidKey::MediaStepBackward => "KEYCODE_MEDIA_STEP_BACKWARD",
+            AndroidKey::SoftSleep => "KEYCODE_SOFT_SLEEP",
+            AndroidKey::Cut => "KEYCODE_CUT",
+            AndroidKey::Copy => "KEYCODE_COPY",
+            AndroidKey::Paste => "KEYCODE_PASTE",
+        }
+    }
+
+    /// Finds a key by its Android `KeyEvent` name (e.g. `KEYCODE_BACK`).
+    ///
+    /// The `KEYCODE_` prefix is optional, so `BACK` works as well.
+    pub fn from_name(name: &str) -> Option<AndroidKey> {
+        let name = name.strip_prefix("KEYCODE_").unwrap_or(name);
+
+        match name {
+            "UNKNOWN" => Some(AndroidKey::Unknown),
+            "SOFT_LEFT" => Some(AndroidKey::SoftLeft),
+            "SOFT_RIGHT" => Some(AndroidKey::SoftRight),
+            "HOME" => Some(AndroidKey::Home),
+            "BACK" => Some(AndroidKey::Back),
+            "CALL" => Some(AndroidKey::Call),
+            "ENDCALL" => Some(AndroidKey::EndCall),
+            "0" => Some(AndroidKey::Digit0),
+            "1" => Some(AndroidKey::Digit1),
+            "2" => Some(AndroidKey::Digit2),
+            "3" => Some(AndroidKey::Digit3),
+            "4" => Some(AndroidKey::Digit4),
+            "5" => Some(AndroidKey::Digit5),
+            "6" => Some(AndroidKey::Digit6),
+            "7" => Some(AndroidKey::Digit7),
+            "8" => Some(AndroidKey::Digit8),
+            "9" => Some(AndroidKey::Digit9),
+            "STAR" => Some(AndroidKey::Asterisk),
+            "POUND" => Some(AndroidKey::Pound),
+            "DPAD_UP" => Some(AndroidKey::DPadUp),
+            "DPAD_DOWN" => Some(AndroidKey::DPadDown),
+            "DPAD_LEFT" => Some(AndroidKey::DPadLeft),
+            "DPAD_RIGHT" => Some(AndroidKey::DpadRight),
+            "DPAD_CENTER" => Some(AndroidKey::DPadCenter),
+            "VOLUME_UP" => Some(AndroidKey::VolumeUp),
+            "VOLUME_DOWN" => Some(AndroidKey::VolumeDown),
+            "POWER" => Some(AndroidKey::Power),
+            "CAMERA" => Some(AndroidKey::Camera),
+            "CLEAR" => Some(AndroidKey::Clear),
+            "A" => Some(AndroidKey::A),
+            "B" => Some(AndroidKey::B),
+            "C" => Some(AndroidKey::C),
+            "D" => Some(AndroidKey::D),
+            "E" => Some(AndroidKey::E),
+            "F" => Some(AndroidKey::F),
+            "G" => Some(AndroidKey::G),
+            "H" => Some(AndroidKey::H),
+            "I" => Some(AndroidKey::I),
+            "J" => Some(AndroidKey::J),
+            "K" => Some(AndroidKey::K),
+            "L" => Some(AndroidKey::L),
+            "M" => Some(AndroidKey::M),
+            "N" => Some(AndroidKey::N),
+            "O" => Some(AndroidKey::O),
+            "P" => Some(AndroidKey::P),
+            "Q" => Some(AndroidKey::Q),
+            "R" => Some(AndroidKey::R),
+            "S" => Some(AndroidKey::S),
+            "T" => Some(AndroidKey::T),
+            "U" => Some(AndroidKey::U),
+            "V" => Some(AndroidKey::V),
+            "W" => Some(AndroidKey::W),
+            "X" => Some(AndroidKey::X),
+            "Y" => Some(AndroidKey::Y),
+            "Z" => Some(AndroidKey::Z),
+            "COMMA" => Some(AndroidKey::Comma),
+            "PERIOD" => Some(AndroidKey::Period),
+            "ALT_LEFT" => Some(AndroidKey::AltLeft),
+            "ALT_RIGHT" => Some(AndroidKey::AltRight),
+            "SHIFT_LEFT" => Some(AndroidKey::ShiftLeft),
+            "SHIFT_RIGHT" => Some(AndroidKey::ShiftRight),
+            "TAB" => Some(AndroidKey::Tab),
+            "SPACE" => Some(AndroidKey::Space),
+            "SYM" => Some(AndroidKey::SYM),
+            "EXPLORER" => Some(AndroidKey::Explorer),
+            "ENVELOPE" => Some(AndroidKey::Envelope),
+            "ENTER" => Some(AndroidKey::Enter),
+            "DEL" => Some(AndroidKey::DEL),
+            "GRAVE" => Some(AndroidKey::Grave),
+            "MINUS" => Some(AndroidKey::Minus),
+            "EQUALS" => Some(AndroidKey::Equals),
+            "LEFT_BRACKET" => Some(AndroidKey::LeftBracket),
+            "RIGHT_BRACKET" => Some(AndroidKey::RightBracket),
+            "BACKSLASH" => Some(AndroidKey::Backslash),
+            "SEMICOLON" => Some(AndroidKey::Semicolon),
+            "APOSTROPHE" => Some(AndroidKey::Apostrophe),
+            "SLASH" => Some(AndroidKey::Slash),
+            "AT" => Some(AndroidKey::At),
+            "NUM" => Some(AndroidKey::NUM),
+            "HEADSETHOOK" => Some(AndroidKey::HeadsetHook),
+            "FOCUS" => Some(AndroidKey::Focus),
+            "PLUS" => Some(AndroidKey::Plus),
+            "MENU" => Some(AndroidKey::Menu),
+            "NOTIFICATION" => Some(AndroidKey::Notification),
+            "SEARCH" => Some(AndroidKey::Search),
+            "MEDIA_PLAY_PAUSE" => Some(AndroidKey::MediaPlayPause),
+            "MEDIA_STOP" => Some(AndroidKey::MediaStop),
+            "MEDIA_NEXT" => Some(AndroidKey::MediaNext),
+            "MEDIA_PREVIOUS" => Some(AndroidKey::MediaPrevious),
+            "MEDIA_REWIND" => Some(AndroidKey::MediaRewind),
+            "MEDIA_FAST_FORWARD" => Some(AndroidKey::MediaFastForward),
+            "MUTE" => Some(AndroidKey::Mute),
+            "PAGE_UP" => Some(AndroidKey::PageUp),
+            "PAGE_DOWN" => Some(AndroidKey::PageDown),
+            "PICTSYMBOLS" => Some(AndroidKey::PictSymbols),
+            "SWITCH_CHARSET" => Some(AndroidKey::SwitchCharset),
+            "BUTTON_A" => Some(AndroidKey::ButtonA),
+            "BUTTON_B" => Some(AndroidKey::ButtonB),
+            "BUTTON_C" => Some(AndroidKey::ButtonC),
+            "BUTTON_X" => Some(AndroidKey::ButtonX),
+            "BUTTON_Y" => Some(AndroidKey::ButtonY),
+            "BUTTON_Z" => Some(AndroidKey::ButtonZ),
+            "BUTTON_L1" => Some(AndroidKey::ButtonL1),
+            "BUTTON_R1" => Some(AndroidKey::ButtonR1),
+            "BUTTON_L2" => Some(AndroidKey::ButtonL2),
+            "BUTTON_R2" => Some(AndroidKey::ButtonR2),
+            "BUTTON_THUMBL" => Some(AndroidKey::ButtonThumbL),
+            "BUTTON_THUMBR" => Some(AndroidKey::ButtonThumbR),
+            "BUTTON_START" => Some(AndroidKey::ButtonStart),
+            "BUTTON_SELECT" => Some(AndroidKey::ButtonSelect),
+            "BUTTON_MODE" => Some(AndroidKey::ButtonMode),
+            "ESCAPE" => Some(AndroidKey::Escape),
+            "FORWARD_DEL" => Some(AndroidKey::ForwardDel),
+            "CTRL_LEFT" => Some(AndroidKey::CtrlLeft),
+            "CTRL_RIGHT" => Some(AndroidKey::CtrlRight),
+            "CAPS_LOCK" => Some(AndroidKey::CapsLock),
+            "SCROLL_LOCK" => Some(AndroidKey::ScrollLock),
+            "META_LEFT" => Some(AndroidKey::MetaLeft),
+            "META_RIGHT" => Some(AndroidKey::MetaRight),
+            "FUNCTION" => Some(AndroidKey::Function),
+            "SYSRQ" => Some(AndroidKey::SYSRQ),
+            "BREAK" => Some(AndroidKey::Break),
+            "MOVE_HOME" => Some(AndroidKey::MoveHome),
+            "MOVE_END" => Some(AndroidKey::MoveEnd),
+            "INSERT" => Some(AndroidKey::Insert),
+            "FORWARD" => Some(AndroidKey::Forward),
+            "MEDIA_PLAY" => Some(AndroidKey::MediaPlay),
+            "MEDIA_PAUSE" => Some(AndroidKey::MediaPause),
+            "MEDIA_CLOSE" => Some(AndroidKey::MediaClose),
+            "MEDIA_EJECT" => Some(AndroidKey::MediaEject),
+            "MEDIA_RECORD" => Some(AndroidKey::MediaRecord),
+            "F1" => Some(AndroidKey::F1),
+            "F2" => Some(AndroidKey::F2),
+            "F3" => Some(AndroidKey::F3),
+            "F4" => Some(AndroidKey::F4),
+            "F5" => Some(AndroidKey::F5),
+            "F6" => Some(AndroidKey::F6),
+            "F7" => Some(AndroidKey::F7),
+            "F8" => Some(AndroidKey::F8),
+            "F9" => Some(AndroidKey::F9),
+            "F10" => Some(AndroidKey::F10),
+            "F11" => Some(AndroidKey::F11),
+            "F12" => Some(AndroidKey::F12),
+            "NUM_LOCK" => Some(AndroidKey::NumLock),
+            "NUMPAD_0" => Some(AndroidKey::Numpad0),
+            "NUMPAD_1" => Some(AndroidKey::Numpad1),
+            "NUMPAD_2" => Some(AndroidKey::Numpad2),
+            "NUMPAD_3" => Some(AndroidKey::Numpad3),
+            "NUMPAD_4" => Some(AndroidKey::Numpad4),
+            "NUMPAD_5" => Some(AndroidKey::Numpad5),
+            "NUMPAD_6" => Some(AndroidKey::Numpad6),
+            "NUMPAD_7" => Some(AndroidKey::Numpad7),
+            "NUMPAD_8" => Some(AndroidKey::Numpad8),
+            "NUMPAD_9" => Some(AndroidKey::Numpad9),
+            "NUMPAD_DIVIDE" => Some(AndroidKey::NumpadDivide),
+            "NUMPAD_MULTIPLY" => Some(AndroidKey::NumpadMultiply),
+            "NUMPAD_SUBTRACT" => Some(AndroidKey::NumpadSubtract),
+            "NUMPAD_ADD" => Some(AndroidKey::NumpadAdd),
+            "NUMPAD_DOT" => Some(AndroidKey::NumpadDot),
+            "NUMPAD_COMMA" => Some(AndroidKey::NumpadComma),
+            "NUMPAD_ENTER" => Some(AndroidKey::NumpadEnter),
+            "NUMPAD_EQUALS" => Some(AndroidKey::NumpadEquals),
+            "NUMPAD_LEFT_PAREN" => Some(AndroidKey::NumpadLeftParen),
+            "NUMPAD_RIGHT_PAREN" => Some(AndroidKey::NumpadRightParen),
+            "VOLUME_MUTE" => Some(AndroidKey::VolumeMute),
+            "INFO" => Some(AndroidKey::Info),
+            "CHANNEL_UP" => Some(AndroidKey::ChannelUp),
+            "CHANNEL_DOWN" => Some(AndroidKey::ChannelDown),
+            "ZOOM_IN" => Some(AndroidKey::KeycodeZoomIn),
+            "ZOOM_OUT" => Some(AndroidKey::KeycodeZoomOut),
+            "TV" => Some(AndroidKey::TV),
+            "WINDOW" => Some(AndroidKey::Window),
+            "GUIDE" => Some(AndroidKey::Guide),
+            "DVR" => Some(AndroidKey::DVR),
+            "BOOKMARK" => Some(AndroidKey::Bookmark),
+            "CAPTIONS" => Some(AndroidKey::Captions),
+            "SETTINGS" => Some(AndroidKey::Settings),
+            "TV_POWER" => Some(AndroidKey::TVPower),
+            "TV_INPUT" => Some(AndroidKey::TVInput),
+            "STB_POWER" => Some(AndroidKey::STBPower),
+            "STB_INPUT" => Some(AndroidKey::STBInput),
+            "AVR_POWER" => Some(AndroidKey::AVRPower),
+            "AVR_INPUT" => Some(AndroidKey::AVRInput),
+            "PROG_RED" => Some(AndroidKey::ProgRed),
+            "PROG_GREEN" => Some(AndroidKey::ProgGreen),
+            "PROG_YELLOW" => Some(AndroidKey::ProgYellow),
+            "PROG_BLUE" => Some(AndroidKey::ProgBlue),
+            "APP_SWITCH" => Some(AndroidKey::AppSwitch),
+            "BUTTON_1" => Some(AndroidKey::Button1),
+            "BUTTON_2" => Some(AndroidKey::Button2),
+            "BUTTON_3" => Some(AndroidKey::Button3),
+            "BUTTON_4" => Some(AndroidKey::Button4),
+            "BUTTON_5" => Some(AndroidKey::Button5),
+            "BUTTON_6" => Some(AndroidKey::Button6),
+            "BUTTON_7" => Some(AndroidKey::Button7),
+            "BUTTON_8" => Some(AndroidKey::Button8),
+            "BUTTON_9" => Some(AndroidKey::Button9),
+            "BUTTON_10" => Some(AndroidKey::Button10),
+            "BUTTON_11" => Some(AndroidKey::Button11),
+            "BUTTON_12" => Some(AndroidKey::Button12),
+            "BUTTON_13" => Some(AndroidKey::Button13),
+            "BUTTON_14" => Some(AndroidKey::Button14),
+            "BUTTON_15" => Some(AndroidKey::Button15),
+            "BUTTON_16" => Some(AndroidKey::Button16),
+            "LANGUAGE_SWITCH" => Some(AndroidKey::LanguageSwitch),
+            "MANNER_MODE" => Some(AndroidKey::MannerMode),
+            "3D_MODE" => Some(AndroidKey::Mode3D),
+            "CONTACTS" => Some(AndroidKey::Contacts),
+            "CALENDAR" => Some(AndroidKey::Calendar),
+            "MUSIC" => Some(AndroidKey::Music),
+            "CALCULATOR" => Some(AndroidKey::Calculator),
+            "ZENKAKU_HANKAKU" => Some(AndroidKey::ZenkakuHankaku),
+            "EISU" => Some(AndroidKey::Eisu),
+            "MUHENKAN" => Some(AndroidKey::Muhenkan),
+            "HENKAN" => Some(AndroidKey::Henkan),
+            "KATAKANA_HIRAGANA" => Some(AndroidKey::KatakanaHiragana),
+            "YEN" => Some(AndroidKey::Yen),
+            "RO" => Some(AndroidKey::Ro),
+            "KANA" => Some(AndroidKey::Kana),
+            "ASSIST" => Some(AndroidKey::Assist),
+            "BRIGHTNESS_DOWN" => Some(AndroidKey::BrightnessDown),
+            "BRIGHTNESS_UP" => Some(AndroidKey::BrightnessUp),
+            "MEDIA_AUDIO_TRACK" => Some(AndroidKey::MediaAudioTrack),
+            "SLEEP" => Some(AndroidKey::Sleep),
+            "WAKEUP" => Some(AndroidKey::WakeUp),
+            "PAIRING" => Some(AndroidKey::Pairing),
+            "MEDIA_TOP_MENU" => Some(AndroidKey::MediaTopMenu),
+            "11" => Some(AndroidKey::Key11),
+            "12" => Some(AndroidKey::Key12),
+            "LAST_CHANNEL" => Some(AndroidKey::LastChannel),
+            "TV_DATA_SERVICE" => Some(AndroidKey::TVDataService),
+            "VOICE_ASSIST" => Some(AndroidKey::VoiceAssist),
+            "TV_RADIO_SERVICE" => Some(AndroidKey::TVRadioService),
+            "TV_TELETEXT" => Some(AndroidKey::TVTeletext),
+            "TV_NUMBER_ENTRY" => Some(AndroidKey::TVNumberEntry),
+            "TV_TERRESTRIAL_ANALOG" => Some(AndroidKey::TVTerrestrialAnalog),
+            "TV_TERRESTRIAL_DIGITAL" => Some(AndroidKey::TVTerrestrialDigital),
+            "TV_SATELLITE" => Some(AndroidKey::TVSatellite),
+            "TV_SATELLITE_BS" => Some(AndroidKey::TVSatelliteBS),
+            "TV_SATELLITE_CS" => Some(AndroidKey::TVSatelliteCS),
+            "TV_SATELLITE_SERVICE" => Some(AndroidKey::TVSatelliteService),
+            "TV_NETWORK" => Some(AndroidKey::TVNetwork),
+            "TV_ANTENNA_CABLE" => Some(AndroidKey::TVAntennaCable),
+            "TV_INPUT_HDMI_1" => Some(AndroidKey::TVInputHdmi1),
+            "TV_INPUT_HDMI_2" => Some(AndroidKey::TVInputHdmi2),
+            "TV_INPUT_HDMI_3" => Some(AndroidKey::TVInputHdmi3),
+            "TV_INPUT_HDMI_4" => Some(AndroidKey::TVInputHdmi4),
+            "TV_INPUT_COMPOSITE_1" => Some(AndroidKey::TVInputComposite1),
+            "TV_INPUT_COMPOSITE_2" => Some(AndroidKey::TVInputComposite2),
+            "TV_INPUT_COMPONENT_1" => Some(AndroidKey::TVInputComponent1),
+            "TV_INPUT_COMPONENT_2" => Some(AndroidKey::TVInputComponent2),
+            "TV_INPUT_VGA_1" => Some(AndroidKey::TVInputVga1),
+            "TV_AUDIO_DESCRIPTION" => Some(AndroidKey::TVAudioDescription),
+            "TV_AUDIO_DESCRIPTION_MIX_UP" => Some(AndroidKey::TVAudioDescriptionMixUp),
+            "TV_AUDIO_DESCRIPTION_MIX_DOWN" => Some(AndroidKey::TVAudioDescriptionMixDown),
+            "TV_ZOOM_MODE" => Some(AndroidKey::TVZoomMode),
+            "TV_CONTENTS_MENU" => Some(AndroidKey::TVContentsMenu),
+            "TV_MEDIA_CONTEXT_MENU" => Some(AndroidKey::TVMediaContextMenu),
+            "TV_TIMER_PROGRAMMING" => Some(AndroidKey::TVTimerProgramming),
+            "HELP" => Some(AndroidKey::Help),
+            "NAVIGATE_PREVIOUS" => Some(AndroidKey::NavigatePrevious),
+            "NAVIGATE_NEXT" => Some(AndroidKey::NavigateNext),
+            "NAVIGATE_IN" => Some(AndroidKey::NavigateIn),
+            "NAVIGATE_OUT" => Some(AndroidKey::NavigateOut),
+            "STEM_PRIMARY" => Some(AndroidKey::StemPrimary),
+            "STEM_1" => Some(AndroidKey::Stem1),
+            "STEM_2" => Some(AndroidKey::Stem2),
+            "STEM_3" => Some(AndroidKey::Stem3),
+            "DPAD_UP_LEFT" => Some(AndroidKey::DpadUpLeft),
+            "DPAD_DOWN_LEFT" => Some(AndroidKey::DpadDownLeft),
+            "DPAD_UP_RIGHT" => Some(AndroidKey::DpadUpRight),
+            "DPAD_DOWN_RIGHT" => Some(AndroidKey::DpadDownRight),
+            "MEDIA_SKIP_FORWARD" => Some(AndroidKey::MediaSkipForward),
+            "MEDIA_SKIP_BACKWARD" => Some(AndroidKey::MediaSkipBackward),
+            "MEDIA_STEP_FORWARD" => Some(AndroidKey::MediaStepForward),
+            "MEDIA_STEP_BACKWARD" => Some(AndroidKey::MediaStepBackward),
+            "SOFT_SLEEP" => Some(AndroidKey::SoftSleep),
+            "CUT" => Some(AndroidKey::Cut),
+            "COPY" => Some(AndroidKey::Copy),
+            "PASTE" => Some(AndroidKey::Paste),
+            _ => None,
+        }
+    }
+}
+
 
 bitflags::bitflags! {
     #[repr(transparent)]
@@ -1363,3 +2068,129 @@ bitflags::bitflags! {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use serde_json::json;
+    use tokio::time::Instant;
+    use crate::commands::keyboard::{AndroidKey, EditorAction, HasOnScreenKeyboard, HidesKeyboard, KeyMacroStep, PerformsEditorAction, PressesBack, PressesKey};
+    use crate::mock::{android_client, ios_client, MockResponse, MockTransport};
+
+    #[tokio::test]
+    async fn back_out_of_app_is_detected() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("com.example")));
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("com.android.launcher")));
+        let client = android_client(&transport).await;
+
+        assert!(client.press_back_and_detect_exit().await.unwrap());
+
+        let press = &transport.requests()[2];
+        assert_eq!(press.path, "/session/mock-session/appium/device/press_keycode");
+        assert_eq!(press.body.as_ref().unwrap()["keycode"], 4);
+    }
+
+    #[tokio::test]
+    async fn back_consumed_by_app_is_not_exit() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!("com.example")));
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!("com.example")));
+        let client = android_client(&transport).await;
+
+        assert!(!client.press_back_and_detect_exit().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn keyboard_is_hidden_with_mobile_command() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.mobile_hide_keyboard().await.unwrap();
+
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/execute/sync");
+        assert_eq!(request.body.unwrap(), json!({ "script": "mobile: hideKeyboard", "args": [{}] }));
+    }
+
+    #[tokio::test]
+    async fn keyboard_shown_is_read_with_mobile_command() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(true)));
+        let client = android_client(&transport).await;
+
+        assert!(client.mobile_keyboard_shown().await.unwrap());
+        assert_eq!(transport.requests().pop().unwrap().body.unwrap()["script"], "mobile: isKeyboardShown");
+    }
+
+    #[tokio::test]
+    async fn editor_action_is_sent_by_name() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.perform_editor_action(EditorAction::Search).await.unwrap();
+
+        assert_eq!(transport.requests().pop().unwrap().body.unwrap(), json!({
+            "script": "mobile: performEditorAction",
+            "args": [{ "action": "search" }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn key_macro_steps_are_performed_in_order() {
+        let transport = MockTransport::new();
+        for _ in 0..3 {
+            transport.respond(MockResponse::value(json!(null)));
+        }
+        let client = android_client(&transport).await;
+        let start = Instant::now();
+
+        client.press_key_macro(&[
+            KeyMacroStep::Press(AndroidKey::DPadDown.into()),
+            KeyMacroStep::Delay(Duration::from_millis(100)),
+            KeyMacroStep::Press(AndroidKey::DPadDown.into()),
+            KeyMacroStep::LongPress(AndroidKey::DPadCenter.into()),
+        ]).await.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        let presses: Vec<_> = transport.requests().into_iter().skip(1)
+            .map(|request| (request.path, request.body.unwrap()["keycode"].clone()))
+            .collect();
+        assert_eq!(presses, vec![
+            ("/session/mock-session/appium/device/press_keycode".to_string(), json!(20)),
+            ("/session/mock-session/appium/device/press_keycode".to_string(), json!(20)),
+            ("/session/mock-session/appium/device/long_press_keycode".to_string(), json!(23)),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn key_macro_stops_at_failed_press() {
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+
+        let result = client.press_key_macro(&[
+            KeyMacroStep::Press(AndroidKey::DPadDown.into()),
+            KeyMacroStep::Press(AndroidKey::DPadDown.into()),
+        ]).await;
+
+        assert!(result.is_err());
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn recents_are_opened_with_app_switch_keycode() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.open_recents().await.unwrap();
+
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/appium/device/press_keycode");
+        assert_eq!(request.body.unwrap(), json!({ "keycode": 187, "metastate": 0, "flags": 0 }));
+    }
+}
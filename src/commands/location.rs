@@ -5,7 +5,7 @@ use http::Method;
 use serde_derive::{Deserialize, Serialize};
 use serde_json::json;
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Location {
@@ -32,7 +32,7 @@ pub trait SupportsLocation : AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("location", value)
     }
 
     /// Tries to set location if the driver/device supports it. Returns location of device after the attempt.
@@ -48,7 +48,7 @@ pub trait SupportsLocation : AppiumClientTrait {
             }))
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("location", value)
     }
 }
 
@@ -58,12 +58,35 @@ impl SupportsLocation for AndroidClient {}
 #[async_trait]
 impl SupportsLocation for IOSClient {}
 
-#[derive(Clone, Debug, Serialize)]
+/// Location with Android-specific fields
+///
+/// When read from the device, fields other than latitude and longitude may be missing (then they're `0`).
+///
+/// ```
+/// use appium_client::commands::location::AndroidGeoLocation;
+///
+/// let location: AndroidGeoLocation = serde_json::from_str(r#"{
+///     "latitude": 52.23,
+///     "longitude": 21.01,
+///     "altitude": 100.0,
+///     "satellites": 12,
+///     "speed": 1.5
+/// }"#).unwrap();
+/// assert_eq!(location.satellites, 12);
+///
+/// let location: AndroidGeoLocation = serde_json::from_str(r#"{"latitude": 52.23, "longitude": 21.01}"#).unwrap();
+/// assert_eq!(location.satellites, 0);
+/// assert_eq!(location.speed, 0.0);
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AndroidGeoLocation {
     pub latitude: f64,
     pub longitude: f64,
+    #[serde(default)]
     pub altitude: f64,
+    #[serde(default)]
     pub satellites: u32,
+    #[serde(default)]
     pub speed: f64,
 }
 
@@ -79,7 +102,7 @@ impl AndroidGeoLocation {
     }
 }
 
-/// Set android geolocation (with extended options)
+/// Get or set android geolocation (with extended options)
 #[async_trait]
 pub trait SupportsAndroidLocation : AppiumClientTrait {
     async fn set_android_location(&self, location: AndroidGeoLocation) -> Result<Location, CmdError> {
@@ -91,10 +114,109 @@ pub trait SupportsAndroidLocation : AppiumClientTrait {
             }))
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("location", value)
     }
 
+    /// Reads current location with Android-specific fields (satellites and speed), if the driver reports them.
+    async fn android_location_detailed(&self) -> Result<AndroidGeoLocation, CmdError> {
+        let value = self.issue_cmd(AppiumCommand::Custom(
+            Method::GET,
+            "location".to_string(),
+            None
+        )).await?;
+
+        parse_response("location", value)
+    }
 }
 
 #[async_trait]
-impl SupportsAndroidLocation for AndroidClient {}
\ No newline at end of file
+impl SupportsAndroidLocation for AndroidClient {}
+
+/// Set simulated geolocation on iOS Simulator
+#[async_trait]
+pub trait SupportsIOSLocation : AppiumClientTrait {
+    /// Sets simulated location of iOS Simulator (`mobile: setSimulatedLocation`).
+    ///
+    /// Latitude must be in range -90..=90 and longitude in range -180..=180.
+    /// Altitude is passed only if given, as it is supported only by newer simulators.
+    async fn set_ios_location(&self, latitude: f64, longitude: f64, altitude: Option<f64>) -> Result<(), CmdError> {
+        if !(-90f64..=90f64).contains(&latitude) {
+            return Err(CmdError::InvalidArgument(
+                "latitude".to_string(),
+                format!("{latitude} should be between -90 and 90.")
+            ))
+        }
+
+        if !(-180f64..=180f64).contains(&longitude) {
+            return Err(CmdError::InvalidArgument(
+                "longitude".to_string(),
+                format!("{longitude} should be between -180 and 180.")
+            ))
+        }
+
+        let mut args = json!({
+            "latitude": latitude,
+            "longitude": longitude
+        });
+
+        if let Some(altitude) = altitude {
+            args["altitude"] = json!(altitude);
+        }
+
+        self.execute("mobile: setSimulatedLocation", vec![args]).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SupportsIOSLocation for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use fantoccini::error::CmdError;
+    use serde_json::json;
+    use crate::commands::location::SupportsIOSLocation;
+    use crate::mock::{ios_client, MockResponse, MockTransport};
+
+    #[tokio::test]
+    async fn ios_location_is_simulated() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.set_ios_location(52.23, 21.01, None).await.unwrap();
+
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/execute/sync");
+        assert_eq!(request.body.unwrap(), json!({
+            "script": "mobile: setSimulatedLocation",
+            "args": [{ "latitude": 52.23, "longitude": 21.01 }]
+        }));
+    }
+
+    #[tokio::test]
+    async fn ios_location_altitude_is_sent_if_given() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.set_ios_location(52.23, 21.01, Some(100.0)).await.unwrap();
+
+        let body = transport.requests().pop().unwrap().body.unwrap();
+        assert_eq!(body["args"][0]["altitude"], json!(100.0));
+    }
+
+    #[tokio::test]
+    async fn ios_location_out_of_range_is_refused() {
+        let transport = MockTransport::new();
+        let client = ios_client(&transport).await;
+        let requests = transport.requests().len();
+
+        let latitude = client.set_ios_location(91.0, 21.01, None).await;
+        let longitude = client.set_ios_location(52.23, -181.0, None).await;
+
+        assert!(matches!(latitude, Err(CmdError::InvalidArgument(name, _)) if name == "latitude"));
+        assert!(matches!(longitude, Err(CmdError::InvalidArgument(name, _)) if name == "longitude"));
+        assert_eq!(transport.requests().len(), requests);
+    }
+}
@@ -0,0 +1,140 @@
+//! Device logs (e.g. logcat)
+use std::collections::VecDeque;
+use std::time::Duration;
+use async_trait::async_trait;
+use fantoccini::error::CmdError;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use http::Method;
+use serde_derive::Deserialize;
+use serde_json::json;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::commands::{AppiumCommand, parse_response};
+
+/// How long [ReadsLogs::stream_logs] waits before polling again if there were no new entries.
+const LOG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// One entry of device log
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct LogEntry {
+    /// Time of the entry (milliseconds since epoch).
+    pub timestamp: i64,
+    pub level: String,
+    pub message: String,
+}
+
+/// Read device logs
+#[async_trait]
+pub trait ReadsLogs: AppiumClientTrait {
+    /// Types of logs available in this session (e.g. `logcat`, `syslog`, `server`).
+    async fn log_types(&self) -> Result<Vec<String>, CmdError> {
+        let value = self.issue_cmd(AppiumCommand::Custom(
+            Method::GET,
+            "se/log/types".to_string(),
+            None
+        )).await?;
+
+        parse_response("se/log/types", value)
+    }
+
+    /// Returns new log entries of given type, since the last call (or since the session started).
+    async fn get_logs(&self, log_type: &str) -> Result<Vec<LogEntry>, CmdError> {
+        let value = self.issue_cmd(AppiumCommand::Custom(
+            Method::POST,
+            "se/log".to_string(),
+            Some(json!({
+                "type": log_type
+            }))
+        )).await?;
+
+        parse_response("se/log", value)
+    }
+
+    /// Tails logs of given type (e.g. `logcat`) by polling [ReadsLogs::get_logs].
+    ///
+    /// If there are no new entries, the stream waits a second before polling again.
+    /// The stream ends after the first error.
+    ///
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # use appium_client::AndroidClient;
+    /// # use appium_client::commands::logs::ReadsLogs;
+    /// # async fn tail(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// let mut logs = client.stream_logs("logcat");
+    /// while let Some(entry) = logs.next().await {
+    ///     println!("{}", entry?.message);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn stream_logs(&self, log_type: &str) -> BoxStream<'_, Result<LogEntry, CmdError>>
+        where Self: Sync
+    {
+        let log_type = log_type.to_string();
+        let state = (VecDeque::new(), false);
+
+        stream::unfold(state, move |(mut buffer, failed)| {
+            let log_type = log_type.clone();
+            async move {
+                if failed {
+                    return None;
+                }
+
+                loop {
+                    if let Some(entry) = buffer.pop_front() {
+                        return Some((Ok(entry), (buffer, false)));
+                    }
+
+                    match self.get_logs(&log_type).await {
+                        Ok(entries) if entries.is_empty() => tokio::time::sleep(LOG_POLL_INTERVAL).await,
+                        Ok(entries) => buffer.extend(entries),
+                        Err(e) => return Some((Err(e), (buffer, true))),
+                    }
+                }
+            }
+        }).boxed()
+    }
+}
+
+#[async_trait]
+impl ReadsLogs for AndroidClient {}
+
+#[async_trait]
+impl ReadsLogs for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use http::StatusCode;
+    use serde_json::json;
+    use crate::commands::logs::ReadsLogs;
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    #[tokio::test]
+    async fn streamed_logs_are_yielded_in_order_and_end_after_an_error() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!([
+            {"timestamp": 1, "level": "INFO", "message": "first"},
+            {"timestamp": 2, "level": "WARN", "message": "second"}
+        ])));
+        transport.respond(MockResponse::value(json!([
+            {"timestamp": 3, "level": "INFO", "message": "third"}
+        ])));
+        transport.respond(MockResponse::error(StatusCode::INTERNAL_SERVER_ERROR, "unknown error", "logcat is gone"));
+        let client = android_client(&transport).await;
+
+        let entries: Vec<_> = client.stream_logs("logcat").collect().await;
+
+        let messages: Vec<_> = entries.iter()
+            .filter_map(|entry| entry.as_ref().ok())
+            .map(|entry| entry.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["first", "second", "third"]);
+        assert_eq!(entries.len(), 4);
+        assert!(entries[3].is_err());
+
+        let polls: Vec<_> = transport.requests().into_iter().skip(1).collect();
+        assert_eq!(polls.len(), 3);
+        assert!(polls.iter().all(|request| request.path == "/session/mock-session/se/log"
+            && request.body == Some(json!({"type": "logcat"}))));
+    }
+}
@@ -0,0 +1,276 @@
+//! Multitouch gestures (many fingers at once)
+use std::time::Duration;
+use async_trait::async_trait;
+use fantoccini::actions::{Actions, InputSource, MOUSE_BUTTON_LEFT, PointerAction, TouchActions};
+use fantoccini::error::CmdError;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::commands::gestures::pause;
+
+/// Builder of a gesture performed by many fingers simultaneously.
+///
+/// Each finger is a separate [TouchActions] (a pointer input source).
+/// All fingers are sent in one W3C actions request, so Appium performs them concurrently (tick by tick).
+///
+/// Note: every finger must have an unique name (the name passed to [TouchActions::new]).
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use fantoccini::actions::{InputSource, MOUSE_BUTTON_LEFT, PointerAction, TouchActions};
+/// use appium_client::commands::multitouch::MultiTouch;
+///
+/// let finger = |name: &str, x: i64| TouchActions::new(name.to_string())
+///     .then(PointerAction::MoveTo { duration: None, x, y: 500 })
+///     .then(PointerAction::Down { button: MOUSE_BUTTON_LEFT })
+///     .then(PointerAction::MoveTo { duration: Some(Duration::from_millis(500)), x, y: 100 })
+///     .then(PointerAction::Up { button: MOUSE_BUTTON_LEFT });
+///
+/// // two fingers swiping up at the same time
+/// let gesture = MultiTouch::new()
+///     .finger(finger("finger1", 100))
+///     .finger(finger("finger2", 300));
+/// ```
+#[derive(Debug, Default)]
+pub struct MultiTouch {
+    fingers: Vec<TouchActions>,
+}
+
+impl MultiTouch {
+    pub fn new() -> MultiTouch {
+        MultiTouch {
+            fingers: vec![],
+        }
+    }
+
+    /// Adds a finger (pointer) to the gesture.
+    pub fn finger(mut self, actions: TouchActions) -> Self {
+        self.fingers.push(actions);
+        self
+    }
+
+    /// Adds a pause to every finger added so far, so all of them wait before next actions.
+    ///
+    /// Useful for settle time between gestures. See [crate::commands::gestures::pause] for a pause of one finger.
+    pub fn pause(mut self, duration: Duration) -> Self {
+        self.fingers = self.fingers.into_iter()
+            .map(|finger| finger.then(pause(duration)))
+            .collect();
+        self
+    }
+
+    /// Two fingers rotating around `center` (on opposite sides of a circle with given `radius`), e.g. to rotate a map.
+    ///
+    /// Positive `degrees` rotate clockwise (as seen on screen), negative - counterclockwise.
+    /// The fingers follow arcs (a move every 15 degrees at most), not straight lines, so the rotation is recognized by the app.
+    pub fn rotate(center: (i64, i64), radius: i64, degrees: f64, duration: Duration) -> MultiTouch {
+        let finger = |name: &str, start_degrees: f64| {
+            let points = arc_points(center, radius, start_degrees, degrees);
+            let step_duration = duration / (points.len() as u32 - 1).max(1);
+
+            let (x, y) = points[0];
+            let finger = TouchActions::new(name.to_string())
+                .then(PointerAction::MoveTo { duration: Some(Duration::ZERO), x, y })
+                .then(PointerAction::Down { button: MOUSE_BUTTON_LEFT });
+
+            points[1..].iter()
+                .fold(finger, |finger, &(x, y)| finger.then(PointerAction::MoveTo { duration: Some(step_duration), x, y }))
+                .then(PointerAction::Up { button: MOUSE_BUTTON_LEFT })
+        };
+
+        MultiTouch::new()
+            .finger(finger("finger1", 0.0))
+            .finger(finger("finger2", 180.0))
+    }
+
+    /// Merges all fingers into one actions request.
+    ///
+    /// Returns an error if there are no fingers in this gesture.
+    pub fn into_actions(self) -> Result<Actions, CmdError> {
+        let mut fingers = self.fingers.into_iter();
+        let first = fingers.next()
+            .ok_or_else(|| CmdError::InvalidArgument(
+                "fingers".to_string(),
+                "At least one finger is needed to perform a gesture.".to_string()
+            ))?;
+
+        Ok(fingers.fold(Actions::from(first), |actions, finger| actions.and(finger)))
+    }
+}
+
+impl From<Vec<TouchActions>> for MultiTouch {
+    fn from(fingers: Vec<TouchActions>) -> Self {
+        MultiTouch {
+            fingers,
+        }
+    }
+}
+
+/// Max angle (in degrees) of one move of a finger in [MultiTouch::rotate].
+const ARC_STEP_DEGREES: f64 = 15.0;
+
+/// Points on an arc of a circle, from `start_degrees` to `start_degrees + degrees` (both ends included).
+///
+/// Angles are measured clockwise from the right side of the circle (screen coordinates, where y grows downwards).
+/// There is a point every 15 degrees (at most), so the moves between them approximate the arc.
+pub(crate) fn arc_points(center: (i64, i64), radius: i64, start_degrees: f64, degrees: f64) -> Vec<(i64, i64)> {
+    let steps = (degrees.abs() / ARC_STEP_DEGREES).ceil().max(1.0) as u32;
+
+    (0..=steps)
+        .map(|step| {
+            let angle = (start_degrees + degrees * step as f64 / steps as f64).to_radians();
+            (
+                center.0 + (radius as f64 * angle.cos()).round() as i64,
+                center.1 + (radius as f64 * angle.sin()).round() as i64,
+            )
+        })
+        .collect()
+}
+
+/// Perform gestures with many fingers at once
+#[async_trait]
+pub trait PerformsMultiTouch: AppiumClientTrait {
+    /// Performs all given touch actions concurrently (as one multitouch gesture).
+    async fn perform_multitouch(&self, actions: Vec<TouchActions>) -> Result<(), CmdError> {
+        let actions = MultiTouch::from(actions).into_actions()?;
+        self.perform_actions(actions).await
+    }
+
+    /// Rotates two fingers around `center` by `degrees` (clockwise if positive), see [MultiTouch::rotate].
+    async fn rotate_gesture(&self, center: (i64, i64), radius: i64, degrees: f64, duration: Duration) -> Result<(), CmdError> {
+        let actions = MultiTouch::rotate(center, radius, degrees, duration).into_actions()?;
+        self.perform_actions(actions).await
+    }
+}
+
+#[async_trait]
+impl PerformsMultiTouch for AndroidClient {}
+
+#[async_trait]
+impl PerformsMultiTouch for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use fantoccini::actions::{InputSource, MOUSE_BUTTON_LEFT, PointerAction, TouchActions};
+    use serde_json::json;
+    use crate::commands::multitouch::{arc_points, MultiTouch, PerformsMultiTouch};
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    fn tap(name: &str, x: i64) -> TouchActions {
+        TouchActions::new(name.to_string())
+            .then(PointerAction::MoveTo { duration: None, x, y: 500 })
+            .then(PointerAction::Down { button: MOUSE_BUTTON_LEFT })
+            .then(PointerAction::Up { button: MOUSE_BUTTON_LEFT })
+    }
+
+    #[test]
+    fn arc_goes_clockwise_from_start_angle() {
+        let points = arc_points((500, 500), 100, 0.0, 90.0);
+
+        assert_eq!(points.first(), Some(&(600, 500)));
+        assert_eq!(points.last(), Some(&(500, 600)));
+        assert_eq!(points.len(), 7);
+    }
+
+    #[test]
+    fn arc_of_opposite_finger_starts_on_the_other_side() {
+        let points = arc_points((500, 500), 100, 180.0, 90.0);
+
+        assert_eq!(points.first(), Some(&(400, 500)));
+        assert_eq!(points.last(), Some(&(500, 400)));
+    }
+
+    #[test]
+    fn negative_degrees_go_counterclockwise() {
+        let points = arc_points((500, 500), 100, 0.0, -90.0);
+
+        assert_eq!(points.last(), Some(&(500, 400)));
+    }
+
+    #[test]
+    fn short_arc_has_both_ends() {
+        assert_eq!(arc_points((500, 500), 100, 0.0, 0.0), vec![(600, 500), (600, 500)]);
+    }
+
+    #[test]
+    fn gesture_without_fingers_is_refused() {
+        assert!(MultiTouch::new().into_actions().is_err());
+    }
+
+    #[tokio::test]
+    async fn fingers_are_merged_into_one_request() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.perform_multitouch(vec![tap("finger1", 100), tap("finger2", 300)]).await.unwrap();
+
+        let actions_requests = transport.requests_to("/actions");
+        assert_eq!(actions_requests.len(), 1);
+
+        let body = actions_requests[0].body.clone().unwrap();
+        let sources = body["actions"].as_array().unwrap();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0]["id"], "finger1");
+        assert_eq!(sources[1]["id"], "finger2");
+        assert!(sources.iter().all(|source| source["type"] == "pointer"));
+        assert_eq!(sources[1]["actions"][0]["x"], 300);
+    }
+
+    #[tokio::test]
+    async fn pause_is_added_to_every_finger() {
+        let actions = MultiTouch::new()
+            .finger(tap("finger1", 100))
+            .finger(tap("finger2", 300))
+            .pause(Duration::from_millis(200))
+            .into_actions()
+            .unwrap();
+
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+        client.perform_actions(actions).await.unwrap();
+
+        let body = transport.requests().pop().unwrap().body.unwrap();
+        for source in body["actions"].as_array().unwrap() {
+            let last = source["actions"].as_array().unwrap().last().unwrap();
+            assert_eq!(last["type"], "pause");
+            assert_eq!(last["duration"], 200);
+        }
+    }
+
+    #[tokio::test]
+    async fn rotation_moves_fingers_by_degrees_around_center() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+        let (center, degrees) = ((500, 500), 60.0_f64);
+
+        client.rotate_gesture(center, 100, degrees, Duration::from_millis(600)).await.unwrap();
+
+        // a point rotated clockwise (on screen) around center
+        let rotated = |(x, y): (i64, i64)| {
+            let (dx, dy) = ((x - center.0) as f64, (y - center.1) as f64);
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            (center.0 as f64 + dx * cos - dy * sin, center.1 as f64 + dx * sin + dy * cos)
+        };
+
+        let body = transport.requests().pop().unwrap().body.unwrap();
+        let sources = body["actions"].as_array().unwrap();
+        assert_eq!(sources.len(), 2);
+
+        for source in sources {
+            assert_eq!(source["type"], "pointer");
+            let actions = source["actions"].as_array().unwrap();
+            let point = |action: &serde_json::Value| (action["x"].as_i64().unwrap(), action["y"].as_i64().unwrap());
+            let (start, end) = (point(&actions[0]), point(&actions[actions.len() - 2]));
+
+            let expected = rotated(start);
+            assert!((end.0 as f64 - expected.0).abs() <= 1.0 && (end.1 as f64 - expected.1).abs() <= 1.0,
+                    "{start:?} rotated to {end:?}, expected {expected:?}");
+        }
+
+        let start = |source: &serde_json::Value| (source["actions"][0]["x"].clone(), source["actions"][0]["y"].clone());
+        assert_eq!(start(&sources[0]), (json!(600), json!(500)));
+        assert_eq!(start(&sources[1]), (json!(400), json!(500)));
+    }
+}
@@ -5,7 +5,7 @@ use fantoccini::error::CmdError;
 use http::Method;
 use serde_json::json;
 use crate::{AndroidClient, AppiumClientTrait};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 bitflags! {
     #[repr(transparent)]
@@ -44,7 +44,7 @@ pub trait HasNetworkState: AppiumClientTrait {
             None,
         )).await?;
 
-        let bits: u16 = serde_json::from_value(value)?;
+        let bits: u16 = parse_response("network_connection", value)?;
 
         Ok(ConnectionState::from_bits_truncate(bits))
     }
@@ -7,7 +7,7 @@ use http::Method;
 use serde_derive::Serialize;
 use serde_json::{Error, json, Value};
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 #[derive(Clone, Debug)]
 pub struct ScreenRecordingUploadOptions {
@@ -91,7 +91,7 @@ pub trait CanRecordScreen: AppiumClientTrait {
             })),
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/start_recording_screen", value)
     }
 
     async fn stop_recording_screen(&self) -> Result<String, CmdError> {
@@ -107,7 +107,7 @@ pub trait CanRecordScreen: AppiumClientTrait {
             })),
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/stop_recording_screen", value)
     }
 }
 
@@ -183,6 +183,7 @@ pub trait IOSCanRecordScreen : CanRecordScreen {
     /// **video_scale** - ffmpeg video scaling, none by default (<https://trac.ffmpeg.org/wiki/Scaling>).
     ///
     /// **video_filters** - ffmpeg video filters (eg. `transpose=1`, <https://ffmpeg.org/ffmpeg-filters.html>).
+    #[allow(clippy::too_many_arguments)]
     async fn start_recording(&self,
                              video_codec: Option<String>,
                              video_quality: Option<IOSVideoQuality>,
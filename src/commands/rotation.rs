@@ -1,16 +1,18 @@
 //! Device orientation and rotation
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 use async_trait::async_trait;
 use fantoccini::error::CmdError;
 use http::Method;
 use serde::Deserialize;
 use serde_derive::Serialize;
 use serde_json::{json, Map, Value};
+use tokio::time::{Instant, interval};
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Orientation {
     Landscape,
@@ -54,7 +56,7 @@ pub trait SupportsRotation : AppiumClientTrait {
         let value = self.issue_cmd(AppiumCommand::Custom(
             Method::GET, "orientation".to_string(), None
         )).await?;
-        Ok(serde_json::from_value(value.clone())?)
+        parse_response("orientation", value)
     }
 
     async fn set_orientation(&self, orientation: Orientation) -> Result<Orientation, CmdError> {
@@ -67,9 +69,32 @@ pub trait SupportsRotation : AppiumClientTrait {
         Ok(orientation)
     }
 
+    /// Sets orientation and waits until the device actually rotates (at most `timeout`).
+    ///
+    /// [SupportsRotation::set_orientation] returns immediately, even if the device is still rotating.
+    /// This one re-reads the orientation every 250ms until it matches, or returns [CmdError::WaitTimeout].
+    async fn set_orientation_confirmed(&self, orientation: Orientation, timeout: Duration) -> Result<Orientation, CmdError> {
+        self.set_orientation(orientation).await?;
+
+        let start = Instant::now();
+        let mut interval = interval(Duration::from_millis(250));
+
+        loop {
+            interval.tick().await;
+
+            if self.orientation().await? == orientation {
+                return Ok(orientation);
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(CmdError::WaitTimeout);
+            }
+        }
+    }
+
     async fn rotation(&self) -> Result<DeviceRotation, CmdError> {
         let value = self.issue_cmd(AppiumCommand::Custom(Method::GET, "rotation".to_string(), None)).await?;
-        let rotation: DeviceRotation = serde_json::from_value(value.clone())?;
+        let rotation: DeviceRotation = parse_response("rotation", value)?;
         Ok(rotation)
     }
 
@@ -91,4 +116,48 @@ pub trait SupportsRotation : AppiumClientTrait {
 impl SupportsRotation for AndroidClient {}
 
 #[async_trait]
-impl SupportsRotation for IOSClient {}
\ No newline at end of file
+impl SupportsRotation for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use fantoccini::error::CmdError;
+    use http::Method;
+    use serde_json::{json, Value};
+    use crate::commands::rotation::{Orientation, SupportsRotation};
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    #[tokio::test]
+    async fn confirmed_orientation_polls_until_the_device_rotates() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(Value::Null));
+        transport.respond(MockResponse::value(json!("PORTRAIT")));
+        transport.respond(MockResponse::value(json!("PORTRAIT")));
+        transport.respond(MockResponse::value(json!("LANDSCAPE")));
+        let client = android_client(&transport).await;
+
+        let orientation = client.set_orientation_confirmed(Orientation::Landscape, Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(orientation, Orientation::Landscape);
+        let requests: Vec<_> = transport.requests().into_iter().skip(1).collect();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].method, Method::POST);
+        assert_eq!(requests[0].body, Some(json!({"orientation": "LANDSCAPE"})));
+        assert!(requests[1..].iter().all(|request| request.method == Method::GET
+            && request.path == "/session/mock-session/orientation"));
+    }
+
+    #[tokio::test]
+    async fn confirmed_orientation_times_out_if_the_device_never_rotates() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(Value::Null));
+        for _ in 0..10 {
+            transport.respond(MockResponse::value(json!("PORTRAIT")));
+        }
+        let client = android_client(&transport).await;
+
+        let result = client.set_orientation_confirmed(Orientation::Landscape, Duration::from_millis(300)).await;
+
+        assert!(matches!(result, Err(CmdError::WaitTimeout)));
+    }
+}
@@ -1,11 +1,14 @@
 //! Settings API (<https://appium.io/docs/en/2.1/guides/settings/>)
 use std::collections::HashMap;
+use std::future::Future;
 use async_trait::async_trait;
 use fantoccini::error::CmdError;
 use http::Method;
+use log::warn;
 use serde_json::{json, Map, Value};
+use serde_repr::Serialize_repr;
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 /// Set or get setting from Settings API (<https://appium.io/docs/en/2.1/guides/settings/>)
 #[async_trait]
@@ -23,6 +26,25 @@ pub trait HasSettings : AppiumClientTrait {
         Ok(())
     }
 
+    /// Updates settings and returns all settings after the update.
+    ///
+    /// Some Appium versions respond with the effective settings, others with nothing.
+    /// In the latter case, the settings are read with [HasSettings::get_settings].
+    async fn update_settings(&self, values: Map<String, Value>) -> Result<HashMap<String, Value>, CmdError> {
+        let value = self.issue_cmd(AppiumCommand::Custom(
+            Method::POST,
+            "appium/settings".to_string(),
+            Some(json!({
+                "settings": Value::Object(values)
+            }))
+        )).await?;
+
+        match value {
+            Value::Object(settings) if !settings.is_empty() => Ok(settings.into_iter().collect()),
+            _ => self.get_settings().await,
+        }
+    }
+
     async fn set_setting(&self, name: &str, value: Value) -> Result<(), CmdError> {
         let mut map = Map::new();
         map.insert(name.to_string(), value);
@@ -37,12 +59,302 @@ pub trait HasSettings : AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/settings", value)
+    }
+
+    /// Sets a setting for the time of running `body`, then sets the previous value back.
+    ///
+    /// The previous value is restored even if `body` fails (and then the error of `body` is returned).
+    /// If `body` panics or the returned future is dropped, the previous value is restored in background.
+    ///
+    /// Returns [CmdError::InvalidArgument] (without changing anything) if the driver doesn't report the setting,
+    /// as there would be no value to restore.
+    ///
+    /// ```no_run
+    ///# use serde_json::json;
+    ///# use appium_client::capabilities::android::AndroidCapabilities;
+    ///# use appium_client::ClientBuilder;
+    ///# use appium_client::commands::settings::HasSettings;
+    ///# use appium_client::find::{AppiumFind, By};
+    ///# #[tokio::main]
+    ///# async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///# let client = ClientBuilder::native(AndroidCapabilities::new_uiautomator())
+    ///#     .connect("http://localhost:4723/")
+    ///#     .await?;
+    /// let found = client.with_setting("allowInvisibleElements", json!(true), || async {
+    ///     client.find_all_by(By::class_name("android.widget.TextView")).await
+    /// }).await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    async fn with_setting<F, Fut, T>(&self, name: &str, value: Value, body: F) -> Result<T, CmdError>
+        where F: FnOnce() -> Fut + Send,
+              Fut: Future<Output=Result<T, CmdError>> + Send,
+              T: Send
+    {
+        let previous = self.get_settings().await?
+            .remove(name)
+            .ok_or_else(|| CmdError::InvalidArgument(
+                name.to_string(),
+                "the setting is not reported by the driver, so it couldn't be restored".to_string(),
+            ))?;
+        self.set_setting(name, value).await?;
+
+        let restore = SettingRestore {
+            client: self.deref().clone(),
+            name: name.to_string(),
+            previous: Some(previous),
+        };
+
+        let result = body().await;
+        let restored = restore.restore().await;
+        if result.is_ok() {
+            restored?;
+        }
+
+        result
+    }
+
+    /// Sets `screenshotQuality` setting.
+    ///
+    /// Lower quality means faster screenshots, which is a common tweak to speed up tests on CI.
+    async fn set_screenshot_quality(&self, quality: ScreenshotQuality) -> Result<(), CmdError> {
+        self.set_setting("screenshotQuality", serde_json::to_value(quality)?).await
+    }
+
+    /// Sets `allowInvisibleElements` setting.
+    ///
+    /// By default, some drivers skip invisible (e.g. offscreen) elements in page source and finds.
+    /// Set it to `true` if you need to locate them.
+    async fn allow_invisible_elements(&self, value: bool) -> Result<(), CmdError> {
+        self.set_setting("allowInvisibleElements", json!(value)).await
+    }
+
+    /// Sets `snapshotMaxDepth` setting, the maximum depth of element tree in page source (and for finds).
+    ///
+    /// Deep trees make page source large and slow. Limiting the depth makes the server return less,
+    /// but elements deeper than the limit can't be found.
+    async fn set_snapshot_max_depth(&self, depth: u32) -> Result<(), CmdError> {
+        self.set_setting("snapshotMaxDepth", json!(depth)).await
+    }
+
+    /// Sets `enforceXPath1` setting (UiAutomator2 only).
+    ///
+    /// By default, newer UiAutomator2 versions evaluate XPath queries with XPath 2.0.
+    /// Set it to `true` to use XPath 1.0, which is how older versions (and other drivers) behave.
+    async fn enforce_xpath1(&self, value: bool) -> Result<(), CmdError> {
+        self.set_setting("enforceXPath1", json!(value)).await
+    }
+
+    /// Sets `normalizeTagNames` setting (UiAutomator2 only).
+    ///
+    /// Android class names may contain characters that are not valid in XML tag names (e.g. `$` of inner classes).
+    /// Set it to `true` to replace them, so that XPath queries on such elements work.
+    async fn normalize_tag_names(&self, value: bool) -> Result<(), CmdError> {
+        self.set_setting("normalizeTagNames", json!(value)).await
+    }
+}
+
+/// Previous value of a setting changed by [HasSettings::with_setting].
+///
+/// If it's dropped before [SettingRestore::restore] (`body` panicked or was cancelled), the value is restored in background.
+struct SettingRestore {
+    client: fantoccini::Client,
+    name: String,
+    previous: Option<Value>,
+}
+
+impl SettingRestore {
+    async fn restore(mut self) -> Result<(), CmdError> {
+        let Some(previous) = self.previous.clone() else {
+            return Ok(());
+        };
+
+        // taken only after the request, so it's still restored on drop if this is cancelled
+        let restored = restore_setting(&self.client, &self.name, previous).await;
+        self.previous = None;
+        restored
     }
 }
 
+impl Drop for SettingRestore {
+    fn drop(&mut self) {
+        let Some(previous) = self.previous.take() else {
+            return;
+        };
+
+        let client = self.client.clone();
+        let name = std::mem::take(&mut self.name);
+        tokio::spawn(async move {
+            if let Err(e) = restore_setting(&client, &name, previous).await {
+                warn!("Error while restoring setting {name}: {e}");
+            }
+        });
+    }
+}
+
+async fn restore_setting(client: &fantoccini::Client, name: &str, previous: Value) -> Result<(), CmdError> {
+    client.issue_cmd(AppiumCommand::Custom(
+        Method::POST,
+        "appium/settings".to_string(),
+        Some(json!({
+            "settings": {
+                name: previous
+            }
+        }))
+    )).await?;
+
+    Ok(())
+}
+
 #[async_trait]
 impl HasSettings for AndroidClient {}
 
 #[async_trait]
-impl HasSettings for IOSClient {}
\ No newline at end of file
+impl HasSettings for IOSClient {}
+
+/// Quality of screenshots (and screen streaming), see [HasSettings::set_screenshot_quality].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize_repr)]
+#[repr(u8)]
+pub enum ScreenshotQuality {
+    Max = 1,
+    Compressed = 2,
+    Balanced = 3,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use fantoccini::error::CmdError;
+    use http::Method;
+    use serde_json::{json, Value};
+    use crate::commands::settings::{HasSettings, ScreenshotQuality};
+    use crate::mock::{android_client, ios_client, MockResponse, MockTransport};
+
+    fn settings_updates(transport: &MockTransport) -> Vec<Value> {
+        transport.requests_to("/appium/settings").into_iter()
+            .filter(|request| request.method == Method::POST)
+            .filter_map(|request| request.body)
+            .collect()
+    }
+
+    fn respond_settings(transport: &MockTransport) {
+        transport.respond(MockResponse::value(json!({ "allowInvisibleElements": false })));
+        transport.respond(MockResponse::value(json!(null)));
+    }
+
+    #[tokio::test]
+    async fn setting_is_restored_after_failing_body() {
+        let transport = MockTransport::new();
+        respond_settings(&transport);
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        let result: Result<(), CmdError> = client.with_setting("allowInvisibleElements", json!(true), || async {
+            Err(CmdError::NotJson("body failed".to_string()))
+        }).await;
+
+        assert!(matches!(result, Err(CmdError::NotJson(_))));
+        assert_eq!(settings_updates(&transport), vec![
+            json!({ "settings": { "allowInvisibleElements": true } }),
+            json!({ "settings": { "allowInvisibleElements": false } }),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn setting_is_restored_after_cancelled_body() {
+        let transport = MockTransport::new();
+        respond_settings(&transport);
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        let body = client.with_setting("allowInvisibleElements", json!(true), || async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        });
+        assert!(tokio::time::timeout(Duration::from_millis(200), body).await.is_err());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(settings_updates(&transport).last(), Some(&json!({ "settings": { "allowInvisibleElements": false } })));
+    }
+
+    #[tokio::test]
+    async fn unreported_setting_is_not_changed() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({})));
+        let client = android_client(&transport).await;
+
+        let result = client.with_setting("unknownSetting", json!(true), || async { Ok(()) }).await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert!(settings_updates(&transport).is_empty());
+    }
+
+    #[tokio::test]
+    async fn screenshot_quality_is_sent_as_number() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = ios_client(&transport).await;
+
+        client.set_screenshot_quality(ScreenshotQuality::Compressed).await.unwrap();
+
+        assert_eq!(settings_updates(&transport), vec![json!({ "settings": { "screenshotQuality": 2 } })]);
+    }
+
+    #[tokio::test]
+    async fn invisible_elements_are_allowed_with_setting() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.allow_invisible_elements(true).await.unwrap();
+
+        assert_eq!(settings_updates(&transport), vec![json!({ "settings": { "allowInvisibleElements": true } })]);
+    }
+
+    #[tokio::test]
+    async fn xpath_settings_are_sent_by_name() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!(null)));
+        let client = android_client(&transport).await;
+
+        client.enforce_xpath1(true).await.unwrap();
+        client.normalize_tag_names(true).await.unwrap();
+
+        assert_eq!(settings_updates(&transport), vec![
+            json!({ "settings": { "enforceXPath1": true } }),
+            json!({ "settings": { "normalizeTagNames": true } }),
+        ]);
+    }
+
+    fn invisible_elements(value: bool) -> serde_json::Map<String, Value> {
+        json!({ "allowInvisibleElements": value }).as_object().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn update_returns_effective_settings_from_response() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "allowInvisibleElements": true, "enforceXPath1": false })));
+        let client = android_client(&transport).await;
+
+        let settings = client.update_settings(invisible_elements(true)).await.unwrap();
+
+        assert_eq!(settings.get("allowInvisibleElements"), Some(&json!(true)));
+        assert_eq!(settings.len(), 2);
+        assert_eq!(transport.requests().pop().unwrap().method, Method::POST);
+    }
+
+    #[tokio::test]
+    async fn update_reads_settings_if_response_is_empty() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(null)));
+        transport.respond(MockResponse::value(json!({ "allowInvisibleElements": true })));
+        let client = android_client(&transport).await;
+
+        let settings = client.update_settings(invisible_elements(true)).await.unwrap();
+
+        assert_eq!(settings.get("allowInvisibleElements"), Some(&json!(true)));
+        assert_eq!(transport.requests().pop().unwrap().method, Method::GET);
+    }
+}
@@ -0,0 +1,89 @@
+//! Page source helpers
+use async_trait::async_trait;
+use fantoccini::error::CmdError;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+
+/// Get page source of limited size (e.g. for logs)
+#[async_trait]
+pub trait HasTruncatedSource: AppiumClientTrait {
+    /// Gets page source and truncates it to (at most) `max_len` bytes.
+    ///
+    /// Truncated source ends with [TRUNCATION_MARKER] followed by the number of bytes cut off.
+    /// If `max_len` is too short to fit the marker, the marker is cut as well.
+    ///
+    /// The whole source is still transferred from Appium server.
+    /// To make the server return less, limit the depth of the tree
+    /// with [crate::commands::settings::HasSettings::set_snapshot_max_depth].
+    async fn source_truncated(&self, max_len: usize) -> Result<String, CmdError> {
+        let source = self.source().await?;
+        Ok(truncate_source(&source, max_len))
+    }
+}
+
+#[async_trait]
+impl HasTruncatedSource for AndroidClient {}
+
+#[async_trait]
+impl HasTruncatedSource for IOSClient {}
+
+/// Marker appended to truncated source, followed by the number of bytes cut off.
+pub const TRUNCATION_MARKER: &str = "\n... [truncated ";
+
+/// Truncates source to (at most) `max_len` bytes, ending it with a marker that says how much was cut off.
+///
+/// The marker counts into `max_len`. Source is cut at a char boundary, so the result is always valid UTF-8.
+pub(crate) fn truncate_source(source: &str, max_len: usize) -> String {
+    if source.len() <= max_len {
+        return source.to_string();
+    }
+
+    // the number in the marker can't be longer than the whole source length
+    let suffix_len = TRUNCATION_MARKER.len() + source.len().to_string().len() + " bytes]".len();
+    let mut end = max_len.saturating_sub(suffix_len);
+    while !source.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    let mut truncated = format!("{}{TRUNCATION_MARKER}{} bytes]", &source[..end], source.len() - end);
+    // marker is ASCII and the source part is never longer than max_len, so this cuts at a char boundary
+    truncated.truncate(max_len);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "<hierarchy><node text=\"a\"/><node text=\"b\"/></hierarchy>";
+
+    #[test]
+    fn short_source_is_not_truncated() {
+        assert_eq!(truncate_source(SOURCE, 1000), SOURCE);
+        assert_eq!(truncate_source(SOURCE, SOURCE.len()), SOURCE);
+    }
+
+    #[test]
+    fn truncated_source_ends_with_marker() {
+        let truncated = truncate_source(SOURCE, 40);
+
+        assert_eq!(truncated, "<hierarchy><nod\n... [truncated 40 bytes]");
+        assert!(truncated.len() <= 40);
+    }
+
+    #[test]
+    fn source_is_cut_at_char_boundary() {
+        let source = "ąęąęąęąęąęąęąęąęąęąęąęąęąęąęąęąęąęąę";
+
+        let truncated = truncate_source(source, 32);
+
+        assert!(truncated.len() <= 32);
+        assert!(truncated.starts_with("ąęą"));
+        assert!(truncated.contains(TRUNCATION_MARKER));
+    }
+
+    #[test]
+    fn marker_is_clamped_to_max_len() {
+        assert_eq!(truncate_source(SOURCE, 10), "\n... [trun");
+        assert_eq!(truncate_source(SOURCE, 0), "");
+    }
+}
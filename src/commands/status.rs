@@ -0,0 +1,79 @@
+//! Status of Appium server
+use std::collections::HashMap;
+use async_trait::async_trait;
+use fantoccini::error::CmdError;
+use http::Method;
+use serde_derive::Deserialize;
+use serde_json::Value;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::commands::{AppiumCommand, parse_response};
+
+/// Status of Appium server (response of `status` endpoint)
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ServerStatus {
+    #[serde(default)]
+    pub ready: bool,
+    pub message: Option<String>,
+    pub build: Option<BuildInfo>,
+    /// Other fields of the status, e.g. info about drivers (this depends on server version and installed plugins).
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
+}
+
+/// Info about Appium server build
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct BuildInfo {
+    /// Version of Appium server (e.g. `2.1.3`).
+    pub version: String,
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
+}
+
+/// Check status of Appium server
+#[async_trait]
+pub trait HasServerStatus: AppiumClientTrait {
+    /// Returns status of Appium server, including its version.
+    ///
+    /// Useful if some features depend on server (or driver) version.
+    async fn server_status(&self) -> Result<ServerStatus, CmdError> {
+        let value = self.issue_cmd(AppiumCommand::CustomGlobal(
+            Method::GET,
+            "status".to_string(),
+            None
+        )).await?;
+
+        parse_response("status", value)
+    }
+}
+
+#[async_trait]
+impl HasServerStatus for AndroidClient {}
+
+#[async_trait]
+impl HasServerStatus for IOSClient {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::commands::status::HasServerStatus;
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    #[tokio::test]
+    async fn status_is_read_outside_of_session() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({
+            "ready": true,
+            "message": "The server is ready to accept new connections",
+            "build": { "version": "2.1.3", "git-sha": "abc" },
+            "drivers": ["uiautomator2"]
+        })));
+        let client = android_client(&transport).await;
+
+        let status = client.server_status().await.unwrap();
+
+        assert!(status.ready);
+        assert_eq!(status.build.unwrap().version, "2.1.3");
+        assert_eq!(status.other.get("drivers"), Some(&json!(["uiautomator2"])));
+        assert_eq!(transport.requests().pop().unwrap().path, "/status");
+    }
+}
@@ -5,7 +5,7 @@ use fantoccini::error::CmdError;
 use http::Method;
 use serde_json::json;
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 /// Retrieve localized app strings (text used in app)
 #[async_trait]
@@ -17,7 +17,7 @@ pub trait HasAppStrings : AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/app/strings", value)
     }
 
     async fn app_strings(&self, lang: &str) -> Result<HashMap<String, String>, CmdError> {
@@ -29,7 +29,7 @@ pub trait HasAppStrings : AppiumClientTrait {
             }))
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/app/strings", value)
     }
 
     async fn app_strings_from_file(&self, lang: &str, file: &str) -> Result<HashMap<String, String>, CmdError> {
@@ -42,7 +42,7 @@ pub trait HasAppStrings : AppiumClientTrait {
             }))
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/app/strings", value)
     }
 }
 
@@ -0,0 +1,89 @@
+//! Reading text from screen
+use async_trait::async_trait;
+use fantoccini::elements::Element;
+use fantoccini::error::CmdError;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::find::{AppiumFind, By};
+
+/// Collect all text visible on screen
+#[async_trait]
+pub trait ReadsVisibleText: AppiumClientTrait {
+    /// Returns text of all elements on screen that have non-empty text (in the order of page source).
+    ///
+    /// Handy for smoke assertions (e.g. "is this message anywhere on screen").
+    /// Note: it fetches text of every such element, so it can take a while on complex screens.
+    async fn all_visible_text(&self) -> Result<Vec<String>, CmdError>;
+}
+
+#[async_trait]
+impl ReadsVisibleText for AndroidClient {
+    async fn all_visible_text(&self) -> Result<Vec<String>, CmdError> {
+        let elements = self.find_all_by(By::uiautomator("new UiSelector().textMatches(\".+\")")).await?;
+        collect_text(elements).await
+    }
+}
+
+#[async_trait]
+impl ReadsVisibleText for IOSClient {
+    async fn all_visible_text(&self) -> Result<Vec<String>, CmdError> {
+        let elements = self.find_all_by(By::ios_ns_predicate("label != '' AND visible == 1")).await?;
+        collect_text(elements).await
+    }
+}
+
+async fn collect_text(elements: Vec<Element>) -> Result<Vec<String>, CmdError> {
+    let mut texts = Vec::with_capacity(elements.len());
+
+    for element in elements {
+        let text = element.text().await?;
+        if !text.trim().is_empty() {
+            texts.push(text);
+        }
+    }
+
+    Ok(texts)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::commands::text::ReadsVisibleText;
+    use crate::mock::{android_client, ios_client, MockResponse, MockTransport};
+
+    fn respond_with_texts(transport: &MockTransport) {
+        transport.respond(MockResponse::elements(&["title-1", "spacer-1", "button-1"]));
+        transport.respond(MockResponse::value(json!("Welcome")));
+        transport.respond(MockResponse::value(json!("  ")));
+        transport.respond(MockResponse::value(json!("Continue")));
+    }
+
+    #[tokio::test]
+    async fn android_text_is_collected_without_blank_text() {
+        let transport = MockTransport::new();
+        respond_with_texts(&transport);
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.all_visible_text().await.unwrap(), vec!["Welcome", "Continue"]);
+
+        let find = transport.requests_to("/elements").remove(0);
+        assert_eq!(find.body.unwrap(), json!({
+            "using": "-android uiautomator",
+            "value": "new UiSelector().textMatches(\".+\")"
+        }));
+    }
+
+    #[tokio::test]
+    async fn ios_text_is_collected_without_blank_text() {
+        let transport = MockTransport::new();
+        respond_with_texts(&transport);
+        let client = ios_client(&transport).await;
+
+        assert_eq!(client.all_visible_text().await.unwrap(), vec!["Welcome", "Continue"]);
+
+        let find = transport.requests_to("/elements").remove(0);
+        assert_eq!(find.body.unwrap(), json!({
+            "using": "-ios predicate string",
+            "value": "label != '' AND visible == 1"
+        }));
+    }
+}
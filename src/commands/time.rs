@@ -4,7 +4,7 @@ use fantoccini::error::CmdError;
 use http::Method;
 use serde_json::json;
 use crate::{AndroidClient, AppiumClientTrait, IOSClient};
-use crate::commands::AppiumCommand;
+use crate::commands::{AppiumCommand, parse_response};
 
 /// Get device time
 #[async_trait]
@@ -19,7 +19,7 @@ pub trait HasDeviceTime : AppiumClientTrait {
             None
         )).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("appium/device/system_time", value)
     }
 
     /// Gets device date and time for both iOS and Android devices with given format.
@@ -31,7 +31,7 @@ pub trait HasDeviceTime : AppiumClientTrait {
             json!({"format": format})
         ]).await?;
 
-        Ok(serde_json::from_value(value)?)
+        parse_response("mobile: getDeviceTime", value)
     }
 }
 
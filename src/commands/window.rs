@@ -0,0 +1,218 @@
+//! Window (screen) size
+use async_trait::async_trait;
+use fantoccini::error::CmdError;
+use http::Method;
+use log::debug;
+use crate::{AndroidClient, AppiumClientTrait, IOSClient};
+use crate::commands::{AppiumCommand, parse_response};
+use crate::commands::android::{SystemBar, SystemBars};
+
+/// Size of the app window (usually the size of device screen), in pixels.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WindowSize {
+    pub width: u64,
+    pub height: u64,
+}
+
+impl WindowSize {
+    pub fn new(width: u64, height: u64) -> WindowSize {
+        WindowSize {
+            width, height
+        }
+    }
+
+    /// Width divided by height.
+    ///
+    /// Returns 0 if height is 0.
+    pub fn aspect_ratio(&self) -> f64 {
+        if self.height == 0 {
+            return 0f64;
+        }
+
+        self.width as f64 / self.height as f64
+    }
+
+    /// Whether the window is taller than it is wide.
+    pub fn is_portrait(&self) -> bool {
+        self.height > self.width
+    }
+
+    /// Whether the window is wider than it is tall.
+    pub fn is_landscape(&self) -> bool {
+        self.width > self.height
+    }
+
+    /// Center point of the window (x, y).
+    pub fn center(&self) -> (u64, u64) {
+        (self.width / 2, self.height / 2)
+    }
+
+    /// Whether the point (x, y) in pixels is inside the window.
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as u64) < self.width && (y as u64) < self.height
+    }
+
+    /// Converts a point given as fractions (0.0..=1.0) of the window into pixels (x, y).
+    ///
+    /// For example, `(0.5, 0.8)` is the horizontal center at 80% of the window height.
+    pub fn point_at(&self, (x, y): (f64, f64)) -> Result<(i64, i64), CmdError> {
+        validate_fraction("x", x)?;
+        validate_fraction("y", y)?;
+
+        Ok((
+            (self.width as f64 * x) as i64,
+            (self.height as f64 * y) as i64,
+        ))
+    }
+}
+
+impl From<(u64, u64)> for WindowSize {
+    fn from((width, height): (u64, u64)) -> Self {
+        WindowSize::new(width, height)
+    }
+}
+
+fn validate_fraction(name: &str, value: f64) -> Result<(), CmdError> {
+    if !(0.0..=1.0).contains(&value) {
+        return Err(CmdError::InvalidArgument(
+            name.to_string(),
+            format!("{value} should be a fraction of the window (between 0.0 and 1.0)")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Get the size of window (screen)
+#[async_trait]
+pub trait HasWindowSize: AppiumClientTrait {
+    /// Same as `get_window_size`, but returns a struct instead of a tuple (so you don't swap width and height by accident).
+    async fn window_size(&self) -> Result<WindowSize, CmdError> {
+        Ok(self.get_window_size().await?.into())
+    }
+}
+
+#[async_trait]
+impl HasWindowSize for AndroidClient {}
+
+#[async_trait]
+impl HasWindowSize for IOSClient {}
+
+/// Part of the window where the content of the app is (not covered by visible system bars), as `(x, y, width, height)`.
+///
+/// Only Android reports system bars (`appium/device/system_bars`), so on other platforms (or if the bars can't be read)
+/// it's the whole window.
+pub(crate) async fn usable_rect(client: &fantoccini::Client) -> Result<(f64, f64, f64, f64), CmdError> {
+    let (x, y, width, height) = client.get_window_rect().await?;
+    let window = (x as f64, y as f64, width as f64, height as f64);
+
+    let bars = client.issue_cmd(AppiumCommand::Custom(Method::GET, "appium/device/system_bars".to_string(), None)).await
+        .and_then(|value| parse_response::<SystemBars>("appium/device/system_bars", value));
+
+    match bars {
+        Ok(bars) => Ok(without_bar(without_bar(window, &bars.status_bar), &bars.navigation_bar)),
+        Err(e) => {
+            debug!("No system bars ({e}), the whole window is usable");
+            Ok(window)
+        }
+    }
+}
+
+/// Point (x, y) in pixels at given fractions (0.0..=1.0) of `rect`.
+pub(crate) fn point_in(rect: (f64, f64, f64, f64), (x, y): (f64, f64)) -> (i64, i64) {
+    let (left, top, width, height) = rect;
+    ((left + width * x) as i64, (top + height * y) as i64)
+}
+
+/// Cuts a visible bar off the edge of `rect` that it's at (a wide bar is at the top or bottom, a tall one at a side).
+pub(crate) fn without_bar(rect: (f64, f64, f64, f64), bar: &SystemBar) -> (f64, f64, f64, f64) {
+    let (x, y, width, height) = rect;
+    if !bar.visible || bar.width <= 0 || bar.height <= 0 {
+        return rect;
+    }
+
+    let (bar_x, bar_y) = (bar.x as f64, bar.y as f64);
+    let (bar_right, bar_bottom) = (bar_x + bar.width as f64, bar_y + bar.height as f64);
+
+    if bar.width >= bar.height {
+        if bar_y <= y {
+            let top = bar_bottom.clamp(y, y + height);
+            (x, top, width, y + height - top)
+        } else if bar_bottom >= y + height {
+            let bottom = bar_y.clamp(y, y + height);
+            (x, y, width, bottom - y)
+        } else {
+            rect
+        }
+    } else if bar_x <= x {
+        let left = bar_right.clamp(x, x + width);
+        (left, y, x + width - left, height)
+    } else if bar_right >= x + width {
+        let right = bar_x.clamp(x, x + width);
+        (x, y, right - x, height)
+    } else {
+        rect
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::commands::android::SystemBar;
+    use crate::commands::window::{HasWindowSize, WindowSize, without_bar};
+    use crate::mock::{android_client, MockResponse, MockTransport};
+
+    const WINDOW: (f64, f64, f64, f64) = (0.0, 0.0, 1080.0, 2400.0);
+
+    fn bar(x: i64, y: i64, width: i64, height: i64) -> SystemBar {
+        SystemBar {
+            visible: true,
+            x, y, width, height,
+        }
+    }
+
+    #[test]
+    fn status_bar_is_cut_off_the_top() {
+        assert_eq!(without_bar(WINDOW, &bar(0, 0, 1080, 63)), (0.0, 63.0, 1080.0, 2337.0));
+    }
+
+    #[test]
+    fn navigation_bar_is_cut_off_the_bottom() {
+        assert_eq!(without_bar(WINDOW, &bar(0, 2274, 1080, 126)), (0.0, 0.0, 1080.0, 2274.0));
+    }
+
+    #[test]
+    fn navigation_bar_in_landscape_is_cut_off_the_side() {
+        let window = (0.0, 0.0, 2400.0, 1080.0);
+        assert_eq!(without_bar(window, &bar(2274, 0, 126, 1080)), (0.0, 0.0, 2274.0, 1080.0));
+    }
+
+    #[test]
+    fn hidden_bar_is_ignored() {
+        let hidden = SystemBar {
+            visible: false,
+            ..bar(0, 0, 1080, 63)
+        };
+        assert_eq!(without_bar(WINDOW, &hidden), WINDOW);
+    }
+
+    #[tokio::test]
+    async fn window_size_is_read_from_window_rect() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "x": 0, "y": 0, "width": 1080, "height": 2400 })));
+        let client = android_client(&transport).await;
+
+        let size = client.window_size().await.unwrap();
+
+        assert_eq!(size, WindowSize::new(1080, 2400));
+        assert!(size.is_portrait());
+        assert_eq!(size.center(), (540, 1200));
+        assert_eq!(transport.requests().pop().unwrap().path, "/session/mock-session/window/rect");
+    }
+
+    #[test]
+    fn aspect_ratio_of_empty_window_is_zero() {
+        assert_eq!(WindowSize::new(1080, 0).aspect_ratio(), 0.0);
+        assert!(WindowSize::new(2400, 1080).is_landscape());
+    }
+}
@@ -0,0 +1,436 @@
+//! Appium-specific helpers for elements
+//!
+//! [Element] comes from fantoccini, so it only knows W3C attributes.
+//! Appium exposes some platform attributes (such as `resource-id` or `bounds` on Android),
+//! and [AppiumElement] reads them in a typed way.
+//!
+//! ```no_run
+//!# use appium_client::AndroidClient;
+//!# use appium_client::element::AppiumElement;
+//!# use appium_client::find::{AppiumFind, By};
+//!# async fn read(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+//! let checkbox = client.find_by(By::id("remember_me")).await?;
+//!
+//! if !checkbox.is_checked().await? {
+//!     let bounds = checkbox.bounds().await?;
+//!     println!("Unchecked checkbox at {:?}", bounds.center());
+//! }
+//!# Ok(())
+//!# }
+//! ```
+use std::sync::Mutex;
+use async_trait::async_trait;
+use fantoccini::Client;
+use fantoccini::elements::Element;
+use fantoccini::error::CmdError;
+use crate::commands::window::usable_rect;
+use crate::find::{AppiumFind, By};
+
+/// Bounds of an element (in pixels), as reported by Appium in `bounds` attribute.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Bounds {
+    pub left: i64,
+    pub top: i64,
+    pub right: i64,
+    pub bottom: i64,
+}
+
+impl Bounds {
+    /// Parses bounds from Android format (`[x,y][x2,y2]`).
+    ///
+    /// ```
+    /// use appium_client::element::Bounds;
+    ///
+    /// let bounds = Bounds::parse("[0,63][1080,210]").unwrap();
+    /// assert_eq!(bounds, Bounds { left: 0, top: 63, right: 1080, bottom: 210 });
+    /// assert_eq!(bounds.width(), 1080);
+    /// assert_eq!(bounds.height(), 147);
+    ///
+    /// assert_eq!(Bounds::parse("[0,63]"), None);
+    /// ```
+    pub fn parse(value: &str) -> Option<Bounds> {
+        let points: Vec<(i64, i64)> = value.trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .split("][")
+            .map(|point| {
+                let (x, y) = point.split_once(',')?;
+                Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+            })
+            .collect::<Option<_>>()?;
+
+        match points[..] {
+            [(left, top), (right, bottom)] => Some(Bounds {
+                left, top, right, bottom
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn width(&self) -> i64 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> i64 {
+        self.bottom - self.top
+    }
+
+    /// Center point of the element (x, y).
+    pub fn center(&self) -> (i64, i64) {
+        ((self.left + self.right) / 2, (self.top + self.bottom) / 2)
+    }
+}
+
+/// Key attributes of an element, for debugging locators (see [crate::find::AppiumFind::dump_matching])
+///
+/// Attributes that the platform doesn't have (e.g. `resource-id` on iOS) are `None`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ElementDump {
+    pub class: Option<String>,
+    pub text: Option<String>,
+    pub content_desc: Option<String>,
+    pub resource_id: Option<String>,
+    pub bounds: Option<Bounds>,
+}
+
+impl ElementDump {
+    /// Reads key attributes of given element.
+    pub async fn of(element: &Element) -> Result<ElementDump, CmdError> {
+        let text = element.text().await?;
+
+        Ok(ElementDump {
+            class: element.class_name().await.ok(),
+            text: Some(text).filter(|text| !text.is_empty()),
+            content_desc: element.content_desc().await.ok().flatten(),
+            resource_id: element.resource_id().await.ok().flatten(),
+            bounds: element.bounds().await.ok(),
+        })
+    }
+}
+
+/// Element that remembers its rect (position and size), so it's fetched only once
+///
+/// Native elements rarely move during a gesture, so there's no need to ask Appium for the rect every time.
+/// If the element might have moved (e.g. after scrolling), call [CachedElement::invalidate].
+///
+/// ```no_run
+///# use appium_client::AndroidClient;
+///# use appium_client::element::CachedElement;
+///# use appium_client::find::{AppiumFind, By};
+///# async fn read(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+/// let slider = CachedElement::new(client.find_by(By::id("volume")).await?);
+///
+/// let (x, y, width, height) = slider.rect_cached().await?;
+/// // no request this time
+/// let (x, y, width, height) = slider.rect_cached().await?;
+///# Ok(())
+///# }
+/// ```
+#[derive(Debug)]
+pub struct CachedElement {
+    element: Element,
+    rect: Mutex<Option<(f64, f64, f64, f64)>>,
+}
+
+impl CachedElement {
+    pub fn new(element: Element) -> CachedElement {
+        CachedElement {
+            element,
+            rect: Mutex::new(None),
+        }
+    }
+
+    /// Rect of the element `(x, y, width, height)`, fetched on first use (or first use after [CachedElement::invalidate]).
+    pub async fn rect_cached(&self) -> Result<(f64, f64, f64, f64), CmdError> {
+        if let Some(rect) = *self.rect.lock().unwrap() {
+            return Ok(rect);
+        }
+
+        let rect = self.element.rectangle().await?;
+        *self.rect.lock().unwrap() = Some(rect);
+        Ok(rect)
+    }
+
+    /// Forgets the rect, so it's fetched again on next [CachedElement::rect_cached].
+    pub fn invalidate(&self) {
+        *self.rect.lock().unwrap() = None;
+    }
+
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+
+    pub fn into_inner(self) -> Element {
+        self.element
+    }
+}
+
+impl From<Element> for CachedElement {
+    fn from(element: Element) -> Self {
+        CachedElement::new(element)
+    }
+}
+
+/// Read Appium attributes of elements
+#[async_trait]
+pub trait AppiumElement {
+    /// `content-desc` attribute (Android), the accessibility id of element.
+    async fn content_desc(&self) -> Result<Option<String>, CmdError>;
+
+    /// `resource-id` attribute (Android), e.g. `com.example:id/button`.
+    async fn resource_id(&self) -> Result<Option<String>, CmdError>;
+
+    /// Whether the `checked` attribute is `true` (e.g. checkboxes or switches on Android).
+    async fn is_checked(&self) -> Result<bool, CmdError>;
+
+    /// `bounds` attribute (Android), parsed.
+    async fn bounds(&self) -> Result<Bounds, CmdError>;
+
+    /// Class of element, e.g. `android.widget.Button` (Android) or `XCUIElementTypeButton` (iOS).
+    ///
+    /// It's the same value that [crate::find::By::class_name] searches by.
+    async fn class_name(&self) -> Result<String, CmdError>;
+
+    /// State of a checkbox or a switch, cross-platform.
+    ///
+    /// Reads `checked` attribute (Android) or `value` attribute (iOS), see [parse_toggle].
+    async fn is_toggled(&self) -> Result<bool, CmdError>;
+
+    /// Whether the whole element is within the window (not clipped by the edges of the screen or covered by system bars).
+    ///
+    /// Unlike `is_displayed`, it returns `false` for elements that are only partially visible, e.g. at the bottom of a list
+    /// (or behind the navigation bar on Android).
+    async fn is_fully_visible(&self, client: &Client) -> Result<bool, CmdError>
+        where Self: Clone + Into<Element> + Sync
+    {
+        let element: Element = self.clone().into();
+        let usable = usable_rect(client).await?;
+
+        Ok(rect_contains(usable, element.rectangle().await?))
+    }
+
+    /// Direct children of element (in document order), e.g. to walk the element tree.
+    ///
+    /// It's a search by xpath `./*` within the element.
+    async fn children(&self) -> Result<Vec<Element>, CmdError>
+        where Self: Clone + Into<Element> + Sync
+    {
+        let element: Element = self.clone().into();
+        element.find_all_by(By::xpath("./*")).await
+    }
+}
+
+/// Checks if rectangle `inner` is entirely within `outer` (both are `(x, y, width, height)`).
+pub(crate) fn rect_contains(outer: (f64, f64, f64, f64), inner: (f64, f64, f64, f64)) -> bool {
+    let (x, y, width, height) = outer;
+    let (inner_x, inner_y, inner_width, inner_height) = inner;
+
+    inner_x >= x && inner_y >= y
+        && inner_x + inner_width <= x + width
+        && inner_y + inner_height <= y + height
+}
+
+/// Parses the state of a checkbox or a switch, as reported by Appium.
+///
+/// Android reports it as `true`/`false` (in `checked` attribute), iOS as `1`/`0` (in `value` attribute).
+pub(crate) fn parse_toggle(value: &str) -> Option<bool> {
+    match value.trim() {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl AppiumElement for Element {
+    async fn content_desc(&self) -> Result<Option<String>, CmdError> {
+        self.attr("content-desc").await
+    }
+
+    async fn resource_id(&self) -> Result<Option<String>, CmdError> {
+        self.attr("resource-id").await
+    }
+
+    async fn is_checked(&self) -> Result<bool, CmdError> {
+        Ok(self.attr("checked").await?.as_deref() == Some("true"))
+    }
+
+    async fn bounds(&self) -> Result<Bounds, CmdError> {
+        let bounds = self.attr("bounds").await?
+            .ok_or_else(|| CmdError::InvalidArgument("bounds".to_string(), "Element has no bounds attribute".to_string()))?;
+
+        Bounds::parse(&bounds)
+            .ok_or_else(|| CmdError::InvalidArgument("bounds".to_string(), format!("Invalid bounds: {bounds}")))
+    }
+
+    async fn class_name(&self) -> Result<String, CmdError> {
+        // Android calls it "class", iOS calls it "type" (and the other platform rejects the unknown name)
+        let class = match self.attr("class").await {
+            Ok(Some(class)) => Some(class),
+            _ => self.attr("type").await?,
+        };
+
+        class.filter(|class| !class.is_empty())
+            .ok_or_else(|| CmdError::InvalidArgument("class".to_string(), "Element has no class or type attribute".to_string()))
+    }
+
+    async fn is_toggled(&self) -> Result<bool, CmdError> {
+        // Android has "checked", iOS reports the state in "value"
+        let state = match self.attr("checked").await {
+            Ok(Some(checked)) => checked,
+            _ => self.attr("value").await?
+                .ok_or_else(|| CmdError::InvalidArgument("checked".to_string(), "Element has no checked or value attribute".to_string()))?,
+        };
+
+        parse_toggle(&state)
+            .ok_or_else(|| CmdError::InvalidArgument("checked".to_string(), format!("Element is not a toggle, its state is: {state}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fantoccini::elements::{Element, ElementRef};
+    use fantoccini::error::CmdError;
+    use http::StatusCode;
+    use serde_json::json;
+    use crate::element::{AppiumElement, CachedElement, parse_toggle, rect_contains};
+    use crate::mock::{android_client, ios_client, MockResponse, MockTransport};
+
+    const WINDOW: (f64, f64, f64, f64) = (0.0, 0.0, 1080.0, 2400.0);
+
+    #[test]
+    fn android_toggle_state_is_parsed() {
+        assert_eq!(parse_toggle("true"), Some(true));
+        assert_eq!(parse_toggle("false"), Some(false));
+    }
+
+    #[test]
+    fn ios_toggle_state_is_parsed() {
+        assert_eq!(parse_toggle("1"), Some(true));
+        assert_eq!(parse_toggle("0"), Some(false));
+        assert_eq!(parse_toggle(" 1 "), Some(true));
+    }
+
+    #[test]
+    fn other_values_are_not_toggle_states() {
+        assert_eq!(parse_toggle("Wi-Fi"), None);
+        assert_eq!(parse_toggle(""), None);
+    }
+
+    #[test]
+    fn rect_within_window_is_contained() {
+        assert!(rect_contains(WINDOW, (0.0, 200.0, 1080.0, 150.0)));
+        assert!(rect_contains(WINDOW, WINDOW));
+    }
+
+    #[test]
+    fn rect_over_edge_is_not_contained() {
+        assert!(!rect_contains(WINDOW, (0.0, 2300.0, 1080.0, 150.0)));
+        assert!(!rect_contains(WINDOW, (-20.0, 200.0, 100.0, 150.0)));
+    }
+
+    #[tokio::test]
+    async fn element_behind_navigation_bar_is_not_fully_visible() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "x": 0, "y": 0, "width": 1080, "height": 2400 })));
+        transport.respond(MockResponse::value(json!({
+            "statusBar": { "visible": true, "x": 0, "y": 0, "width": 1080, "height": 63 },
+            "navigationBar": { "visible": true, "x": 0, "y": 2274, "width": 1080, "height": 126 }
+        })));
+        transport.respond(MockResponse::value(json!({ "x": 0, "y": 2200, "width": 1080, "height": 150 })));
+        let client = android_client(&transport).await;
+        let element = Element::from_element_id((*client).clone(), ElementRef::from("row-1".to_string()));
+
+        assert!(!element.is_fully_visible(&client).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn children_are_searched_within_element() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::elements(&["child-1", "child-2"]));
+        let client = android_client(&transport).await;
+        let element = Element::from_element_id((*client).clone(), ElementRef::from("list-1".to_string()));
+
+        let children = element.children().await.unwrap();
+
+        assert_eq!(children.len(), 2);
+        let request = transport.requests().pop().unwrap();
+        assert_eq!(request.path, "/session/mock-session/element/list-1/elements");
+        assert_eq!(request.body.unwrap(), json!({ "using": "xpath", "value": "./*" }));
+    }
+
+    #[tokio::test]
+    async fn rect_is_fetched_once_until_invalidated() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!({ "x": 0, "y": 600, "width": 1080, "height": 120 })));
+        transport.respond(MockResponse::value(json!({ "x": 0, "y": 300, "width": 1080, "height": 120 })));
+        let client = android_client(&transport).await;
+        let slider = CachedElement::new(Element::from_element_id((*client).clone(), ElementRef::from("slider-1".to_string())));
+        let rect_fetches = || transport.requests_to("/element/slider-1/rect").len();
+
+        assert_eq!(slider.rect_cached().await.unwrap(), (0.0, 600.0, 1080.0, 120.0));
+        assert_eq!(slider.rect_cached().await.unwrap(), (0.0, 600.0, 1080.0, 120.0));
+        assert_eq!(rect_fetches(), 1);
+
+        slider.invalidate();
+
+        assert_eq!(slider.rect_cached().await.unwrap(), (0.0, 300.0, 1080.0, 120.0));
+        assert_eq!(rect_fetches(), 2);
+    }
+
+    #[tokio::test]
+    async fn android_class_name_is_class_attribute() {
+        let transport = MockTransport::new();
+        transport.respond_to("element/button-1/attribute/class", MockResponse::value(json!("android.widget.Button")));
+        let client = android_client(&transport).await;
+        let element = Element::from_element_id((*client).clone(), ElementRef::from("button-1".to_string()));
+
+        assert_eq!(element.class_name().await.unwrap(), "android.widget.Button");
+    }
+
+    #[tokio::test]
+    async fn ios_class_name_falls_back_to_type_attribute() {
+        let transport = MockTransport::new();
+        transport.respond_to("element/button-1/attribute/class", MockResponse::error(StatusCode::BAD_REQUEST, "invalid argument", "The attribute 'class' is unknown"));
+        transport.respond_to("element/button-1/attribute/type", MockResponse::value(json!("XCUIElementTypeButton")));
+        let client = ios_client(&transport).await;
+        let element = Element::from_element_id((*client).clone(), ElementRef::from("button-1".to_string()));
+
+        assert_eq!(element.class_name().await.unwrap(), "XCUIElementTypeButton");
+    }
+
+    #[tokio::test]
+    async fn empty_class_and_type_are_an_error() {
+        let transport = MockTransport::new();
+        transport.respond_to("element/button-1/attribute/class", MockResponse::value(json!("")));
+        transport.respond_to("element/button-1/attribute/type", MockResponse::value(json!("")));
+        let client = android_client(&transport).await;
+        let element = Element::from_element_id((*client).clone(), ElementRef::from("button-1".to_string()));
+
+        assert!(matches!(element.class_name().await, Err(CmdError::InvalidArgument(name, _)) if name == "class"));
+    }
+
+    #[tokio::test]
+    async fn missing_or_invalid_bounds_are_an_invalid_argument() {
+        let transport = MockTransport::new();
+        transport.respond_to("element/view-1/attribute/bounds", MockResponse::value(json!(null)));
+        transport.respond_to("element/view-2/attribute/bounds", MockResponse::value(json!("[0,63]")));
+        let client = android_client(&transport).await;
+        let missing = Element::from_element_id((*client).clone(), ElementRef::from("view-1".to_string()));
+        let invalid = Element::from_element_id((*client).clone(), ElementRef::from("view-2".to_string()));
+
+        assert!(matches!(missing.bounds().await, Err(CmdError::InvalidArgument(name, _)) if name == "bounds"));
+        assert!(matches!(invalid.bounds().await, Err(CmdError::InvalidArgument(name, _)) if name == "bounds"));
+    }
+
+    #[tokio::test]
+    async fn unknown_toggle_state_is_an_invalid_argument() {
+        let transport = MockTransport::new();
+        transport.respond_to("element/cell-1/attribute/checked", MockResponse::error(StatusCode::BAD_REQUEST, "invalid argument", "The attribute 'checked' is unknown"));
+        transport.respond_to("element/cell-1/attribute/value", MockResponse::value(json!("Wi-Fi")));
+        let client = ios_client(&transport).await;
+        let element = Element::from_element_id((*client).clone(), ElementRef::from("cell-1".to_string()));
+
+        assert!(matches!(element.is_toggled().await, Err(CmdError::InvalidArgument(name, _)) if name == "checked"));
+    }
+}
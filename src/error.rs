@@ -0,0 +1,250 @@
+//! Errors of Appium client
+//!
+//! Most functions of this lib return [CmdError] (the error of [fantoccini]).
+//! It's opaque when it comes to Appium specifics, so this module adds [AppiumError],
+//! which tells apart errors that need special handling (such as an expired session).
+//!
+//! ```no_run
+//!# use appium_client::AndroidClient;
+//!# use appium_client::error::AppiumError;
+//!# use appium_client::find::{AppiumFind, By};
+//!# async fn find(client: &AndroidClient) -> Result<(), AppiumError> {
+//! match client.find_by(By::id("button")).await.map_err(AppiumError::from) {
+//!     Err(AppiumError::SessionExpired(_)) => {
+//!         // e.g. newCommandTimeout passed, so create a new client (reconnect)
+//!     },
+//!     result => { result?; },
+//! }
+//!# Ok(())
+//!# }
+//! ```
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+pub use fantoccini::error::{CmdError, NewSessionError};
+
+/// Messages Appium sends when the session is gone (e.g. after `newCommandTimeout`).
+const SESSION_EXPIRED_MESSAGES: [&str; 3] = [
+    "session not found",
+    "terminated or not started",
+    "session is either terminated",
+];
+
+/// Error of a command, with Appium-specific cases told apart
+#[derive(Debug)]
+pub enum AppiumError {
+    /// The session does not exist anymore (it ended or timed out due to inactivity).
+    ///
+    /// Every next command will fail as well, so you need to create a new session.
+    SessionExpired(CmdError),
+
+    /// Any other error
+    Cmd(CmdError),
+}
+
+impl AppiumError {
+    pub fn is_session_expired(&self) -> bool {
+        matches!(self, AppiumError::SessionExpired(_))
+    }
+
+    /// The underlying error of fantoccini.
+    pub fn into_inner(self) -> CmdError {
+        match self {
+            AppiumError::SessionExpired(e)
+            | AppiumError::Cmd(e) => e,
+        }
+    }
+}
+
+impl From<CmdError> for AppiumError {
+    fn from(error: CmdError) -> Self {
+        if is_session_expired(&error) {
+            AppiumError::SessionExpired(error)
+        } else {
+            AppiumError::Cmd(error)
+        }
+    }
+}
+
+impl Display for AppiumError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppiumError::SessionExpired(e) => write!(f, "session expired: {e}"),
+            AppiumError::Cmd(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for AppiumError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppiumError::SessionExpired(e)
+            | AppiumError::Cmd(e) => Some(e),
+        }
+    }
+}
+
+/// Error of one of the commands issued in a batch (see [crate::Client::issue_all])
+#[derive(Debug)]
+pub struct BatchError {
+    /// Index of the failed command (the commands after it were not issued).
+    pub index: usize,
+    pub error: CmdError,
+}
+
+impl Display for BatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "command #{} failed: {}", self.index, self.error)
+    }
+}
+
+impl Error for BatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Errors of apps that could not be terminated (see [crate::commands::apps::InteractsWithApps::terminate_apps])
+#[derive(Debug)]
+pub struct TerminateAppsError {
+    /// Bundle ids (packages) of failed apps with their errors, in the order of termination.
+    pub failures: Vec<(String, CmdError)>,
+}
+
+impl Display for TerminateAppsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to terminate {} app(s):", self.failures.len())?;
+        for (bundle_id, error) in &self.failures {
+            write!(f, " {bundle_id} ({error});")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for TerminateAppsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.failures.first()
+            .map(|(_, error)| error as &(dyn Error + 'static))
+    }
+}
+
+/// Error of input commands that check the text of the field afterwards
+/// (see [crate::commands::input::SetsValue::clear_verified] and [crate::commands::input::TypesSecure::type_secure])
+#[derive(Debug)]
+pub enum InputError {
+    /// The element (given id) still has some text after clearing.
+    NotCleared {
+        element: String,
+        text: String,
+    },
+
+    /// The field (given id) has a different number of characters than typed (e.g. the keyboard dropped some).
+    WrongLength {
+        element: String,
+        actual: usize,
+        expected: usize,
+    },
+
+    /// Command failed.
+    Cmd(CmdError),
+}
+
+impl From<CmdError> for InputError {
+    fn from(error: CmdError) -> Self {
+        InputError::Cmd(error)
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotCleared { element, text } => write!(f, "element {element} still has text after clearing: {text}"),
+            InputError::WrongLength { element, actual, expected } => write!(f, "element {element} has {actual} characters, but {expected} were typed"),
+            InputError::Cmd(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Cmd(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Checks if the error means that the session does not exist anymore.
+///
+/// ```
+/// use serde_json::json;
+/// use appium_client::error::{CmdError, is_session_expired};
+///
+/// let error = CmdError::NotW3C(json!({
+///     "value": {
+///         "error": "invalid session id",
+///         "message": "A session is either terminated or not started"
+///     }
+/// }));
+/// assert!(is_session_expired(&error));
+/// ```
+pub fn is_session_expired(error: &CmdError) -> bool {
+    match error {
+        CmdError::Standard(e) => e.error() == "invalid session id"
+            || is_expiry_message(&e.message),
+        CmdError::NotW3C(value) => {
+            let error = value.pointer("/value/error").and_then(|v| v.as_str());
+            let message = value.pointer("/value/message").and_then(|v| v.as_str());
+
+            error == Some("invalid session id")
+                || message.map(is_expiry_message).unwrap_or(false)
+        },
+        _ => false,
+    }
+}
+
+fn is_expiry_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    SESSION_EXPIRED_MESSAGES.iter()
+        .any(|expired| message.contains(expired))
+}
+
+/// Proxy url that can't be used (see [crate::transport::ProxyConfig::parse])
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidProxyError {
+    pub url: String,
+    pub reason: String,
+}
+
+impl Display for InvalidProxyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid proxy {}: {}", self.url, self.reason)
+    }
+}
+
+impl Error for InvalidProxyError {}
+
+/// Connection to Appium server that the client refuses to make (see [crate::ClientBuilder::connect])
+///
+/// It's the source of HTTP error in [NewSessionError::Failed] (or in [CmdError::Failed]).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConnectError {
+    /// `https://` url of Appium server (given host) with a proxy, as there's no tunneling through proxies
+    /// (see [crate::ClientBuilder::with_proxy]).
+    HttpsThroughProxy(String),
+    /// Credentials in the url of Appium server (given host) and `Authorization` header of
+    /// [crate::ClientBuilder::with_auth] at once, as the requests would have two `Authorization` headers.
+    DuplicateAuthorization(String),
+}
+
+impl Display for ConnectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectError::HttpsThroughProxy(host) => write!(f, "https url of {host} can't be used with a proxy, see ClientBuilder::with_proxy"),
+            ConnectError::DuplicateAuthorization(host) => write!(f, "url of {host} has credentials, but Authorization header is already set by ClientBuilder::with_auth"),
+        }
+    }
+}
+
+impl Error for ConnectError {}
@@ -36,6 +36,7 @@
 //!
 //! Notice that if you wish to get only one element (the first match), you can use [AppiumFind::find_by].
 //! If you want all matches on a screen, you use [AppiumFind::find_all_by].
+//! If you only need to know whether an element is on screen, use [AppiumFind::exists].
 //!
 //! ## Custom locator strategy
 //!
@@ -77,13 +78,19 @@
 //! ```
 //!
 use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
 use fantoccini::elements::{Element, ElementRef};
 use fantoccini::Client;
 use fantoccini::error::CmdError;
 use serde::Serializer;
 use serde_derive::Serialize;
-use crate::commands::AppiumCommand;
+use crate::capabilities::AppiumCapability;
+use crate::commands::{AppiumCommand, parse_response};
+use crate::element::ElementDump;
 use async_trait::async_trait;
+use log::debug;
+use tokio::time::{sleep, Instant};
 
 /// Locators supported by Appium
 ///
@@ -103,6 +110,9 @@ pub enum By {
     AccessibilityId(String),
     ClassName(String),
     Image(String),
+    FlutterKey(String),
+    FlutterType(String),
+    FlutterText(String),
     Custom(String),
     CustomKind(String, String)
 }
@@ -184,6 +194,31 @@ impl By {
         By::Image(base64_template.to_string())
     }
 
+    /// Locate a Flutter widget by its `Key` (value of `ValueKey<String>`). (Flutter driver only, in `FLUTTER` context)
+    ///
+    /// See [crate::commands::contexts::SupportsContextSwitching::switch_to_flutter_context].
+    ///
+    /// ```
+    /// use appium_client::find::{By, LocatorParameters};
+    ///
+    /// let locator: LocatorParameters = By::flutter_key("login_button").into();
+    /// assert_eq!(locator.using, "-flutter key");
+    /// assert_eq!(locator.value, "login_button");
+    /// ```
+    pub fn flutter_key(key: &str) -> By {
+        By::FlutterKey(key.to_string())
+    }
+
+    /// Locate a Flutter widget by its type (e.g. `ElevatedButton`). (Flutter driver only, in `FLUTTER` context)
+    pub fn flutter_type(widget_type: &str) -> By {
+        By::FlutterType(widget_type.to_string())
+    }
+
+    /// Locate a Flutter widget by its text. (Flutter driver only, in `FLUTTER` context)
+    pub fn flutter_text(text: &str) -> By {
+        By::FlutterText(text.to_string())
+    }
+
     /// Custom locator for use with plugins registered via the customFindModules capability.
     pub fn custom(query: &str) -> By {
         By::Custom(query.to_string())
@@ -212,6 +247,9 @@ impl From<By> for LocatorParameters {
             By::AccessibilityId(value) => ("accessibility id".to_string(), value),
             By::Image(value) => ("-image".to_string(), value),
             By::ClassName(value) => ("class name".to_string(), value),
+            By::FlutterKey(value) => ("-flutter key".to_string(), value),
+            By::FlutterType(value) => ("-flutter type".to_string(), value),
+            By::FlutterText(value) => ("-flutter text".to_string(), value),
             By::Custom(value) => ("-custom".to_string(), value),
             By::CustomKind(kind, value) => (kind, value)
         };
@@ -237,25 +275,162 @@ pub trait AppiumFind {
 
     /// Locates all elements matching criteria.
     async fn find_all_by(&self, search: By) -> Result<Vec<Element>, CmdError>;
+
+    /// Same as [AppiumFind::find_by], but logs current page source (at debug level) if the element is not found.
+    ///
+    /// It's like `printPageSourceOnFindFailure` capability, but only for this one search.
+    /// By default, it only logs the search (clients and elements of this lib override it to log the source).
+    async fn find_by_debug(&self, search: By) -> Result<Element, CmdError> {
+        let description = format!("{search:?}");
+        let result = self.find_by(search).await;
+        if let Err(CmdError::NoSuchElement(_)) = result {
+            debug!("Element {description} not found");
+        }
+
+        result
+    }
+
+    /// Checks if an element can be located by given strategy.
+    ///
+    /// Returns `Ok(false)` if no such element is found (instead of [CmdError::NoSuchElement]).
+    async fn exists(&self, search: By) -> Result<bool, CmdError> {
+        match self.find_by(search).await {
+            Ok(_) => Ok(true),
+            Err(CmdError::NoSuchElement(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Counts all elements matching criteria.
+    ///
+    /// Returns `Ok(0)` if no such element is found (instead of [CmdError::NoSuchElement]).
+    async fn count(&self, search: By) -> Result<usize, CmdError> {
+        match self.find_all_by(search).await {
+            Ok(elements) => Ok(elements.len()),
+            Err(CmdError::NoSuchElement(_)) => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Finds all matching elements and reads their key attributes (class, text, content-desc, resource-id and bounds).
+    ///
+    /// Useful when writing locators, to see what actually matches. Returns an empty list if nothing matches.
+    ///
+    /// ```no_run
+    ///# use appium_client::AndroidClient;
+    ///# use appium_client::find::{AppiumFind, By};
+    ///# async fn dump(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// for dump in client.dump_matching(By::class_name("android.widget.Button")).await? {
+    ///     println!("{dump:?}");
+    /// }
+    ///# Ok(())
+    ///# }
+    /// ```
+    async fn dump_matching(&self, search: By) -> Result<Vec<ElementDump>, CmdError> {
+        let elements = match self.find_all_by(search).await {
+            Ok(elements) => elements,
+            Err(CmdError::NoSuchElement(_)) => vec![],
+            Err(e) => return Err(e),
+        };
+
+        let mut dumps = Vec::with_capacity(elements.len());
+        for element in &elements {
+            dumps.push(ElementDump::of(element).await?);
+        }
+
+        Ok(dumps)
+    }
+
+    /// Same as [AppiumFind::find_by], but retries on [CmdError::NoSuchElement] for a short grace period.
+    ///
+    /// It's meant for elements that appear "right now" (e.g. a few milliseconds after a tap).
+    /// Retries every [GRACE_RETRY_INTERVAL]. For longer waits use [crate::wait::AppiumWait].
+    async fn find_by_grace(&self, search: By, grace: Duration) -> Result<Element, CmdError> {
+        retry_while(
+            || self.find_by(search.clone()),
+            |result| matches!(result, Err(CmdError::NoSuchElement(_))),
+            grace,
+            GRACE_RETRY_INTERVAL
+        ).await
+    }
+}
+
+/// How often [AppiumFind::find_by_grace] retries the search.
+pub const GRACE_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often the search is retried if [crate::ClientBuilder::implicit_find_wait] is set.
+pub const IMPLICIT_WAIT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Repeats `attempt` (every `interval`) as long as `retry` says so, but not longer than `timeout`.
+///
+/// Returns the last result.
+async fn retry_while<F, Fut, R, T>(mut attempt: F, retry: R, timeout: Duration, interval: Duration) -> Result<T, CmdError>
+    where F: FnMut() -> Fut,
+          Fut: Future<Output = Result<T, CmdError>>,
+          R: Fn(&Result<T, CmdError>) -> bool
+{
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let result = attempt().await;
+        if !retry(&result) || Instant::now() + interval > deadline {
+            return result;
+        }
+
+        sleep(interval).await;
+    }
+}
+
+/// Finds elements like [fantoccini::Client], but waits for them if [crate::ClientBuilder::implicit_find_wait] is set.
+#[async_trait]
+impl<Caps> AppiumFind for crate::Client<Caps>
+    where Caps: AppiumCapability + Send + Sync
+{
+    async fn find_by(&self, search: By) -> Result<Element, CmdError> {
+        let client: &Client = self;
+        if self.implicit_wait.is_zero() {
+            return client.find_by(search).await;
+        }
+
+        retry_while(
+            || client.find_by(search.clone()),
+            |result| matches!(result, Err(CmdError::NoSuchElement(_))),
+            self.implicit_wait,
+            IMPLICIT_WAIT_INTERVAL
+        ).await
+    }
+
+    async fn find_all_by(&self, search: By) -> Result<Vec<Element>, CmdError> {
+        let client: &Client = self;
+        if self.implicit_wait.is_zero() {
+            return client.find_all_by(search).await;
+        }
+
+        retry_while(
+            || client.find_all_by(search.clone()),
+            |result| matches!(result, Ok(elements) if elements.is_empty()) || matches!(result, Err(CmdError::NoSuchElement(_))),
+            self.implicit_wait,
+            IMPLICIT_WAIT_INTERVAL
+        ).await
+    }
+
+    async fn find_by_debug(&self, search: By) -> Result<Element, CmdError> {
+        let description = format!("{search:?}");
+        let result = self.find_by(search).await;
+        log_source_on_miss(self, &description, result).await
+    }
 }
 
 #[async_trait]
 impl AppiumFind for Client {
     async fn find_by(&self, search: By) -> Result<Element, CmdError> {
         let value = self.issue_cmd(AppiumCommand::FindElement(search)).await?;
-        let map: HashMap<String, String> = serde_json::from_value(value.clone())?;
-
-        map.get("ELEMENT")
-            .ok_or_else(|| CmdError::NotW3C(value))
-            .map(|element| Element::from_element_id(
-                self.clone(),
-                ElementRef::from(element.clone())
-            ))
+        element_from_value(self.clone(), value)
     }
 
     async fn find_all_by(&self, search: By) -> Result<Vec<Element>, CmdError> {
         let value = self.issue_cmd(AppiumCommand::FindElements(search)).await?;
-        let result: Vec<HashMap<String, String>> = serde_json::from_value(value)?;
+        let result: Vec<HashMap<String, String>> = parse_response("elements", value)?;
 
         let elements = result.into_iter()
             .filter_map(|map| map.get("ELEMENT").cloned())
@@ -267,6 +442,12 @@ impl AppiumFind for Client {
 
         Ok(elements)
     }
+
+    async fn find_by_debug(&self, search: By) -> Result<Element, CmdError> {
+        let description = format!("{search:?}");
+        let result = self.find_by(search).await;
+        log_source_on_miss(self, &description, result).await
+    }
 }
 
 #[async_trait]
@@ -275,21 +456,14 @@ impl AppiumFind for Element {
         let client = self.clone().client();
         let element_ref = self.element_id();
         let value = client.issue_cmd(AppiumCommand::FindElementWithContext(search, element_ref.to_string())).await?;
-        let map: HashMap<String, String> = serde_json::from_value(value.clone())?;
-
-        map.get("ELEMENT")
-            .ok_or_else(|| CmdError::NotW3C(value))
-            .map(|element| Element::from_element_id(
-                client,
-                ElementRef::from(element.clone())
-            ))
+        element_from_value(client, value)
     }
 
     async fn find_all_by(&self, search: By) -> Result<Vec<Element>, CmdError> {
         let client = self.clone().client();
         let element_ref = self.element_id();
         let value = client.issue_cmd(AppiumCommand::FindElementsWithContext(search, element_ref.to_string())).await?;
-        let result: Vec<HashMap<String, String>> = serde_json::from_value(value)?;
+        let result: Vec<HashMap<String, String>> = parse_response("elements", value)?;
 
         let elements = result.into_iter()
             .filter_map(|map| map.get("ELEMENT").cloned())
@@ -301,4 +475,244 @@ impl AppiumFind for Element {
 
         Ok(elements)
     }
-}
\ No newline at end of file
+
+    async fn find_by_debug(&self, search: By) -> Result<Element, CmdError> {
+        let description = format!("{search:?}");
+        let result = self.find_by(search).await;
+        log_source_on_miss(&self.clone().client(), &description, result).await
+    }
+}
+
+/// Key of element id in W3C responses.
+pub(crate) const W3C_ELEMENT_KEY: &str = "element-6066-11e4-a52e-4f735466cecf";
+
+/// Reads an element from a command response (Appium sends both legacy `ELEMENT` and W3C keys).
+pub(crate) fn element_from_value(client: Client, value: serde_json::Value) -> Result<Element, CmdError> {
+    let map: HashMap<String, String> = parse_response("element", value.clone())?;
+
+    map.get("ELEMENT")
+        .or_else(|| map.get(W3C_ELEMENT_KEY))
+        .ok_or_else(|| CmdError::NotW3C(value))
+        .map(|element| Element::from_element_id(
+            client,
+            ElementRef::from(element.clone())
+        ))
+}
+
+async fn log_source_on_miss(client: &Client, search: &str, result: Result<Element, CmdError>) -> Result<Element, CmdError> {
+    if let Err(CmdError::NoSuchElement(_)) = result {
+        match client.source().await {
+            Ok(source) => debug!("Element {search} not found in page source:\n{source}"),
+            Err(e) => debug!("Element {search} not found, page source unavailable: {e}"),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use http::StatusCode;
+    use serde_json::json;
+    use crate::capabilities::android::AndroidCapabilities;
+    use crate::ClientBuilder;
+    use crate::element::{Bounds, ElementDump};
+    use crate::find::{AppiumFind, By, LocatorParameters};
+    use crate::mock::{MockResponse, MockTransport};
+
+    #[test]
+    fn flutter_locators_use_flutter_strategies() {
+        let locators = [
+            (By::flutter_key("login_button"), "-flutter key", "login_button"),
+            (By::flutter_type("ElevatedButton"), "-flutter type", "ElevatedButton"),
+            (By::flutter_text("Log in"), "-flutter text", "Log in"),
+        ];
+
+        for (by, using, value) in locators {
+            let locator: LocatorParameters = by.into();
+            assert_eq!(locator.using, using);
+            assert_eq!(locator.value, value);
+        }
+    }
+
+    #[tokio::test]
+    async fn implicit_wait_retries_find_until_element_appears() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::no_such_element());
+        transport.respond(MockResponse::no_such_element());
+        transport.respond(MockResponse::element("button-1"));
+        let client = ClientBuilder::with_connector(transport.clone(), AndroidCapabilities::new_uiautomator())
+            .implicit_find_wait(Duration::from_secs(5))
+            .connect("http://localhost:4723/")
+            .await
+            .unwrap();
+
+        let element = client.find_by(By::id("button")).await.unwrap();
+
+        assert_eq!(element.element_id().as_ref(), "button-1");
+        assert_eq!(transport.requests_to("/element").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn implicit_wait_retries_find_all_until_list_is_not_empty() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!([])));
+        transport.respond(MockResponse::elements(&["row-1", "row-2"]));
+        let client = ClientBuilder::with_connector(transport.clone(), AndroidCapabilities::new_uiautomator())
+            .implicit_find_wait(Duration::from_secs(5))
+            .connect("http://localhost:4723/")
+            .await
+            .unwrap();
+
+        let elements = client.find_all_by(By::id("row")).await.unwrap();
+
+        assert_eq!(elements.len(), 2);
+        assert_eq!(transport.requests_to("/elements").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn find_is_not_retried_without_implicit_wait() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::no_such_element());
+        let client = crate::mock::android_client(&transport).await;
+
+        assert!(matches!(client.find_by(By::id("button")).await, Err(fantoccini::error::CmdError::NoSuchElement(_))));
+        assert_eq!(transport.requests_to("/element").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn find_by_debug_reads_source_on_miss() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::no_such_element());
+        transport.respond(MockResponse::value(json!("<hierarchy/>")));
+        let client = crate::mock::android_client(&transport).await;
+
+        let result = client.find_by_debug(By::id("button")).await;
+
+        assert!(matches!(result, Err(fantoccini::error::CmdError::NoSuchElement(_))));
+        assert!(transport.requests().last().unwrap().path.ends_with("/source"));
+    }
+
+    #[tokio::test]
+    async fn find_by_debug_does_not_read_source_on_hit() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::element("button-1"));
+        let client = crate::mock::android_client(&transport).await;
+
+        client.find_by_debug(By::id("button")).await.unwrap();
+
+        assert!(transport.requests().last().unwrap().path.ends_with("/element"));
+    }
+
+    #[tokio::test]
+    async fn element_exists_if_found() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::element("button-1"));
+        transport.respond(MockResponse::no_such_element());
+        let client = crate::mock::android_client(&transport).await;
+
+        assert!(client.exists(By::id("button")).await.unwrap());
+        assert!(!client.exists(By::id("button")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn exists_returns_other_errors() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::error(StatusCode::BAD_REQUEST, "invalid selector", "Unable to parse selector"));
+        let client = crate::mock::android_client(&transport).await;
+
+        assert!(client.exists(By::xpath("//[")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn count_is_number_of_found_elements() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::elements(&["item-1", "item-2", "item-3"]));
+        transport.respond(MockResponse::value(json!([])));
+        let client = crate::mock::android_client(&transport).await;
+
+        assert_eq!(client.count(By::class_name("android.widget.TextView")).await.unwrap(), 3);
+        assert_eq!(client.count(By::class_name("android.widget.TextView")).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn count_is_zero_if_no_such_element() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::no_such_element());
+        let client = crate::mock::android_client(&transport).await;
+
+        assert_eq!(client.count(By::id("item")).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn grace_find_retries_until_element_appears() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::no_such_element());
+        transport.respond(MockResponse::no_such_element());
+        transport.respond(MockResponse::element("toast-1"));
+        let client = crate::mock::android_client(&transport).await;
+
+        let element = client.find_by_grace(By::id("toast"), Duration::from_secs(1)).await.unwrap();
+
+        assert_eq!(element.element_id().as_ref(), "toast-1");
+        assert_eq!(transport.requests_to("/element").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn grace_find_returns_no_such_element_after_grace() {
+        let transport = MockTransport::new();
+        for _ in 0..20 {
+            transport.respond(MockResponse::no_such_element());
+        }
+        let client = crate::mock::android_client(&transport).await;
+
+        let result = client.find_by_grace(By::id("toast"), Duration::from_millis(120)).await;
+
+        assert!(matches!(result, Err(fantoccini::error::CmdError::NoSuchElement(_))));
+        let finds = transport.requests_to("/element").len();
+        assert!((2..=4).contains(&finds), "{finds} finds");
+    }
+
+    #[tokio::test]
+    async fn dumps_are_assembled_from_attributes() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::elements(&["button-1", "label-1"]));
+        let attributes = [
+            ("button-1", "text", json!("Log in")),
+            ("button-1", "attribute/class", json!("android.widget.Button")),
+            ("button-1", "attribute/content-desc", json!("login")),
+            ("button-1", "attribute/resource-id", json!("com.example:id/login")),
+            ("button-1", "attribute/bounds", json!("[0,1800][1080,1950]")),
+            // a view without id, whose bounds are not reported
+            ("label-1", "text", json!("")),
+            ("label-1", "attribute/class", json!("android.widget.TextView")),
+            ("label-1", "attribute/content-desc", json!(null)),
+            ("label-1", "attribute/resource-id", json!(null)),
+            ("label-1", "attribute/bounds", json!(null)),
+        ];
+        for (id, attribute, value) in attributes {
+            transport.respond_to(&format!("element/{id}/{attribute}"), MockResponse::value(value));
+        }
+        let client = crate::mock::android_client(&transport).await;
+
+        let dumps = client.dump_matching(By::xpath("//*[@clickable='true']")).await.unwrap();
+
+        assert_eq!(dumps, vec![
+            ElementDump {
+                class: Some("android.widget.Button".to_string()),
+                text: Some("Log in".to_string()),
+                content_desc: Some("login".to_string()),
+                resource_id: Some("com.example:id/login".to_string()),
+                bounds: Some(Bounds { left: 0, top: 1800, right: 1080, bottom: 1950 }),
+            },
+            ElementDump {
+                class: Some("android.widget.TextView".to_string()),
+                text: None,
+                content_desc: None,
+                resource_id: None,
+                bounds: None,
+            },
+        ]);
+    }
+}
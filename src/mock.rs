@@ -0,0 +1,385 @@
+//! Mock of Appium server, for tests without a real server (requires `test-util` feature)
+//!
+//! [MockTransport] is a connector that you can pass to [crate::ClientBuilder::with_connector].
+//! Instead of connecting to a server, it answers requests with responses you've scripted.
+//! New session and end of session are answered automatically, so you only script responses of commands (in order).
+//!
+//! ```
+//!# use http::Method;
+//!# use serde_json::json;
+//!# use appium_client::capabilities::android::AndroidCapabilities;
+//!# use appium_client::ClientBuilder;
+//!# use appium_client::commands::AppiumCommand;
+//! use appium_client::mock::{MockResponse, MockTransport};
+//!
+//!# #[tokio::main]
+//!# async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let transport = MockTransport::new();
+//! transport.respond(MockResponse::Disconnect);
+//! transport.respond(MockResponse::value(json!(true)));
+//!
+//! let client = ClientBuilder::with_connector(transport.clone(), AndroidCapabilities::new_uiautomator())
+//!     .connect("http://localhost:4723/")
+//!     .await?;
+//!
+//! assert_eq!(client.platform_name(), "android");
+//! assert_eq!(client.automation_name(), Some("UIAutomator2"));
+//!
+//! let command = || AppiumCommand::Custom(Method::GET, "appium/device/is_keyboard_shown".to_string(), None);
+//!
+//! // first attempt fails (the server disconnects), the second one succeeds
+//! assert!(client.issue_cmd(command()).await.is_err());
+//! assert_eq!(client.issue_cmd(command()).await?, json!(true));
+//!
+//! let requests = transport.requests();
+//! assert_eq!(requests.last().unwrap().path, "/session/mock-session/appium/device/is_keyboard_shown");
+//!# Ok(())
+//!# }
+//! ```
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::future::{ready, Ready};
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use http::{header, HeaderMap, Method, Request, Response, StatusCode, Uri};
+use hyper::Body;
+use hyper::client::connect::{Connected, Connection};
+use hyper::server::conn::Http;
+use hyper::service::{service_fn, Service};
+use serde_json::{json, Map, Value};
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+use crate::find::W3C_ELEMENT_KEY;
+
+/// Session id of every session created with [MockTransport].
+pub const MOCK_SESSION_ID: &str = "mock-session";
+
+/// Scripted response of [MockTransport]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockResponse {
+    /// Responds with given status and JSON body.
+    Json(StatusCode, Value),
+    /// Drops the connection without a response (like a crashed server or a network error).
+    Disconnect,
+}
+
+impl MockResponse {
+    /// Successful result of a command (`{"value": ...}`).
+    pub fn value(value: Value) -> MockResponse {
+        MockResponse::Json(StatusCode::OK, json!({
+            "value": value
+        }))
+    }
+
+    /// WebDriver error (e.g. `no such element`) with given status.
+    pub fn error(status: StatusCode, error: &str, message: &str) -> MockResponse {
+        MockResponse::Json(status, json!({
+            "value": {
+                "error": error,
+                "message": message,
+                "stacktrace": ""
+            }
+        }))
+    }
+
+    /// Result of finding an element, as Appium returns it (with both W3C and legacy key).
+    pub fn element(id: &str) -> MockResponse {
+        MockResponse::value(element_value(id))
+    }
+
+    /// Result of finding elements.
+    pub fn elements(ids: &[&str]) -> MockResponse {
+        MockResponse::value(ids.iter().map(|id| element_value(id)).collect())
+    }
+
+    /// `no such element` error of a find.
+    pub fn no_such_element() -> MockResponse {
+        MockResponse::error(StatusCode::NOT_FOUND, "no such element", "An element could not be located")
+    }
+}
+
+fn element_value(id: &str) -> Value {
+    json!({
+        W3C_ELEMENT_KEY: id,
+        "ELEMENT": id
+    })
+}
+
+/// Request received by [MockTransport]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub path: String,
+    pub headers: HeaderMap,
+    pub body: Option<Value>,
+}
+
+/// Connector that answers with scripted responses instead of connecting to a server
+#[derive(Clone, Debug, Default)]
+pub struct MockTransport {
+    responses: Arc<Mutex<VecDeque<MockResponse>>>,
+    command_responses: Arc<Mutex<HashMap<String, VecDeque<MockResponse>>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    server_capabilities: Arc<Mutex<Map<String, Value>>>,
+}
+
+impl MockTransport {
+    pub fn new() -> MockTransport {
+        MockTransport::default()
+    }
+
+    /// Adds a response to the queue. Commands get responses in the order they were added.
+    ///
+    /// If the queue is empty, commands get an `unknown command` error.
+    pub fn respond(&self, response: MockResponse) {
+        self.responses.lock().unwrap().push_back(response);
+    }
+
+    /// Adds a response of given command only (e.g. `appium/device/install_app`), regardless of the order of other commands.
+    ///
+    /// Useful when commands are sent concurrently. These responses are used before the queue of [MockTransport::respond].
+    pub fn respond_to(&self, command: &str, response: MockResponse) {
+        self.command_responses.lock().unwrap()
+            .entry(command.trim_matches('/').to_string())
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Capabilities that the server adds to the session (like a driver picked by Appium), overriding requested ones.
+    ///
+    /// The session has requested capabilities (without `appium:` prefix) and these.
+    pub fn session_capabilities(&self, capabilities: Value) {
+        if let Value::Object(capabilities) = capabilities {
+            self.server_capabilities.lock().unwrap().extend(capabilities);
+        }
+    }
+
+    /// All requests received so far (including new session).
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Requests received so far whose path ends with `suffix` (e.g. `/element` or `/appium/device/lock`).
+    pub fn requests_to(&self, suffix: &str) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().iter()
+            .filter(|request| request.path.ends_with(suffix))
+            .cloned()
+            .collect()
+    }
+
+    fn handle(&self, method: Method, path: String, headers: HeaderMap, body: Option<Value>) -> MockResponse {
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            headers,
+            body: body.clone(),
+        });
+
+        let path = path.trim_end_matches('/');
+        if method == Method::POST && path.ends_with("/session") {
+            let capabilities = self.new_session_capabilities(body.as_ref());
+            return MockResponse::value(json!({
+                "sessionId": MOCK_SESSION_ID,
+                "capabilities": capabilities
+            }));
+        }
+
+        if method == Method::DELETE && path.ends_with(&format!("/session/{MOCK_SESSION_ID}")) {
+            return MockResponse::value(Value::Null);
+        }
+
+        let command = path.strip_prefix(&format!("/session/{MOCK_SESSION_ID}/")).unwrap_or(path);
+        if let Some(response) = self.command_responses.lock().unwrap()
+            .get_mut(command)
+            .and_then(|responses| responses.pop_front()) {
+            return response;
+        }
+
+        self.responses.lock().unwrap()
+            .pop_front()
+            .unwrap_or_else(|| MockResponse::error(StatusCode::NOT_FOUND, "unknown command", "No response scripted in MockTransport"))
+    }
+
+    fn new_session_capabilities(&self, body: Option<&Value>) -> Map<String, Value> {
+        let requested = body
+            .and_then(|body| body.pointer("/capabilities/alwaysMatch"))
+            .and_then(|capabilities| capabilities.as_object())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut capabilities: Map<String, Value> = requested.into_iter()
+            .map(|(name, value)| (name.trim_start_matches("appium:").to_string(), value))
+            .collect();
+        capabilities.extend(self.server_capabilities.lock().unwrap().clone());
+        capabilities
+    }
+}
+
+impl Service<Uri> for MockTransport {
+    type Response = MockStream;
+    type Error = Infallible;
+    type Future = Ready<Result<MockStream, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let transport = self.clone();
+
+        tokio::spawn(async move {
+            let service = service_fn(move |request: Request<Body>| {
+                let transport = transport.clone();
+                async move {
+                    let method = request.method().clone();
+                    let path = request.uri().path().to_string();
+                    let headers = request.headers().clone();
+                    let body = hyper::body::to_bytes(request.into_body()).await
+                        .map_err(io::Error::other)?;
+                    let body = serde_json::from_slice(&body).ok();
+
+                    match transport.handle(method, path, headers, body) {
+                        MockResponse::Json(status, value) => Response::builder()
+                            .status(status)
+                            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+                            .body(Body::from(value.to_string()))
+                            .map_err(io::Error::other),
+                        MockResponse::Disconnect => Err(io::Error::new(io::ErrorKind::ConnectionReset, "disconnected by MockTransport")),
+                    }
+                }
+            });
+
+            // an error here just means that the connection was dropped (e.g. by MockResponse::Disconnect)
+            let _ = Http::new()
+                .http1_only(true)
+                .serve_connection(server, service)
+                .await;
+        });
+
+        ready(Ok(MockStream(client)))
+    }
+}
+
+/// Connection to [MockTransport]
+#[derive(Debug)]
+pub struct MockStream(DuplexStream);
+
+impl Connection for MockStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+impl AsyncRead for MockStream {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for MockStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+/// Client connected to a [MockTransport], for tests of commands.
+#[cfg(test)]
+pub(crate) async fn android_client(transport: &MockTransport) -> crate::AndroidClient {
+    crate::ClientBuilder::with_connector(transport.clone(), crate::capabilities::android::AndroidCapabilities::new_uiautomator())
+        .connect("http://localhost:4723/")
+        .await
+        .expect("mock session should be created")
+}
+
+/// Client of iOS connected to a [MockTransport], for tests of commands.
+#[cfg(test)]
+pub(crate) async fn ios_client(transport: &MockTransport) -> crate::IOSClient {
+    crate::ClientBuilder::with_connector(transport.clone(), crate::capabilities::ios::IOSCapabilities::new_xcui())
+        .connect("http://localhost:4723/")
+        .await
+        .expect("mock session should be created")
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+    use super::*;
+    use crate::commands::AppiumCommand;
+
+    fn keyboard_shown() -> AppiumCommand {
+        AppiumCommand::Custom(Method::GET, "appium/device/is_keyboard_shown".to_string(), None)
+    }
+
+    #[tokio::test]
+    async fn responds_in_order_of_scripting() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(true)));
+        transport.respond(MockResponse::value(json!(false)));
+        let client = android_client(&transport).await;
+
+        assert_eq!(client.issue_cmd(keyboard_shown()).await.unwrap(), json!(true));
+        assert_eq!(client.issue_cmd(keyboard_shown()).await.unwrap(), json!(false));
+    }
+
+    #[tokio::test]
+    async fn unscripted_command_is_unknown() {
+        let transport = MockTransport::new();
+        let client = android_client(&transport).await;
+
+        let error = client.issue_cmd(keyboard_shown()).await.unwrap_err();
+        assert!(error.to_string().contains("No response scripted"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn disconnect_fails_only_one_command() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::Disconnect);
+        transport.respond(MockResponse::value(json!(true)));
+        let client = android_client(&transport).await;
+
+        assert!(client.issue_cmd(keyboard_shown()).await.is_err());
+        assert_eq!(client.issue_cmd(keyboard_shown()).await.unwrap(), json!(true));
+    }
+
+    #[tokio::test]
+    async fn command_responses_go_before_the_queue() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(json!(true)));
+        transport.respond_to("appium/device/lock", MockResponse::value(json!("locked")));
+        let client = android_client(&transport).await;
+
+        let lock = AppiumCommand::Custom(Method::POST, "appium/device/lock".to_string(), None);
+        assert_eq!(client.issue_cmd(lock).await.unwrap(), json!("locked"));
+        assert_eq!(client.issue_cmd(keyboard_shown()).await.unwrap(), json!(true));
+    }
+
+    #[tokio::test]
+    async fn records_requests() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::value(Value::Null));
+        let client = android_client(&transport).await;
+
+        client.issue_cmd(AppiumCommand::Custom(Method::POST, "appium/device/lock".to_string(), Some(json!({"seconds": 1}))))
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].method, Method::POST);
+        assert_eq!(requests[0].path, "/session");
+
+        let last = requests.last().unwrap();
+        assert_eq!(last.method, Method::POST);
+        assert_eq!(last.path, format!("/session/{MOCK_SESSION_ID}/appium/device/lock"));
+        assert_eq!(last.body, Some(json!({"seconds": 1})));
+    }
+}
@@ -94,6 +94,8 @@
 //! So if some elements appear with a delay - then they might not be there.
 //! This method returns immediately after at least one match.
 //!
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
 use std::time::Duration;
 use fantoccini::Client;
 use fantoccini::elements::Element;
@@ -103,28 +105,63 @@ use crate::find::{AppiumFind, By};
 use async_trait::async_trait;
 
 pub trait AppiumWait {
-    fn appium_wait(&self) -> Wait;
+    fn appium_wait(&self) -> Wait<'_>;
 }
 
 impl AppiumWait for Client {
-    fn appium_wait(&self) -> Wait {
-        Wait {
-            client: self,
-            timeout: Duration::from_secs(30),
-            check_delay: Duration::from_millis(250),
-        }
+    fn appium_wait(&self) -> Wait<'_> {
+        Wait::new(self)
+    }
+}
+
+/// Waits for children of an element.
+///
+/// ```no_run
+///# use appium_client::AndroidClient;
+///# use appium_client::find::{AppiumFind, By};
+///# use appium_client::wait::AppiumWait;
+///# async fn wait(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+/// let list = client.find_by(By::id("results")).await?;
+///
+/// // only looks for items inside of the list
+/// let first_item = list
+///     .appium_wait()
+///     .for_element(By::class_name("android.widget.TextView"))
+///     .await?;
+///# Ok(())
+///# }
+/// ```
+impl AppiumWait for Element {
+    fn appium_wait(&self) -> Wait<'_> {
+        Wait::new(self)
     }
 }
 
 /// Wait parameters
-#[derive(Debug)]
 pub struct Wait<'c> {
-    client: &'c Client,
+    client: &'c (dyn AppiumFind + Sync),
     timeout: Duration,
     check_delay: Duration,
 }
 
-impl Wait<'_> {
+impl Debug for Wait<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wait")
+            .field("timeout", &self.timeout)
+            .field("check_delay", &self.check_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'c> Wait<'c> {
+    fn new(client: &'c (dyn AppiumFind + Sync)) -> Wait<'c> {
+        Wait {
+            client,
+            timeout: Duration::from_secs(30),
+            check_delay: Duration::from_millis(250),
+        }
+    }
+
     /// Set the timeout for maximum wait.
     ///
     /// Checks are performed in a loop, with an interval.
@@ -170,37 +207,118 @@ impl Wait<'_> {
             .wait()
             .await
     }
+
+    /// Waits until element's text matches, then returns the element.
+    ///
+    /// Both the text and expected value are trimmed before comparison.
+    /// If the element is not on screen (or gets replaced while checking its text), it's located again in the next check.
+    ///
+    /// ```no_run
+    ///# use appium_client::AndroidClient;
+    ///# use appium_client::find::By;
+    ///# use appium_client::wait::{AppiumWait, TextMatcher};
+    ///# async fn wait(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+    /// client.appium_wait()
+    ///     .for_element_text(By::id("status"), TextMatcher::Equals("Done".to_string()))
+    ///     .await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn for_element_text(self, search: By, matcher: TextMatcher) -> Result<Element, CmdError> {
+        wait_until(|| async {
+            let Some(element) = find_element(&self, search.clone()).await? else {
+                return Ok(None);
+            };
+
+            match element.text().await {
+                Ok(text) if matcher.matches(&text) => Ok(Some(element)),
+                Ok(_) => Ok(None),
+                // fantoccini reports a stale element reference as NoSuchElement too
+                Err(CmdError::NoSuchElement(_)) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }, self.check_delay, self.timeout).await
+    }
+}
+
+/// Expected text of an element (see [Wait::for_element_text])
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TextMatcher {
+    Equals(String),
+    Contains(String),
+}
+
+impl TextMatcher {
+    /// Checks if the text matches (after trimming whitespace).
+    ///
+    /// ```
+    /// use appium_client::wait::TextMatcher;
+    ///
+    /// assert!(TextMatcher::Equals("Done".to_string()).matches(" Done\n"));
+    /// assert!(!TextMatcher::Equals("Done".to_string()).matches("Not done"));
+    /// assert!(TextMatcher::Contains("done".to_string()).matches("Not done "));
+    /// ```
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            TextMatcher::Equals(expected) => text.trim() == expected.trim(),
+            TextMatcher::Contains(expected) => text.trim().contains(expected.trim()),
+        }
+    }
+}
+
+/// Waits until `condition` returns `Some`, then returns its value.
+///
+/// This works like [Wait::for_element], but with any async condition (clipboard content, battery level etc.).
+/// The condition is checked in a loop, with given interval (`check_delay`).
+/// If `timeout` is exceeded, it returns [CmdError::WaitTimeout].
+/// Errors of the condition are returned immediately.
+///
+/// ```no_run
+///# use std::time::Duration;
+///# use appium_client::AndroidClient;
+///# use appium_client::commands::clipboard::HasClipboard;
+///# use appium_client::wait::wait_until;
+///# async fn wait(client: &AndroidClient) -> Result<(), fantoccini::error::CmdError> {
+/// // wait until the app copies something
+/// let text = wait_until(|| async {
+///     let text = client.get_clipboard_text().await?;
+///     Ok(Some(text).filter(|text| !text.is_empty()))
+/// }, Duration::from_millis(500), Duration::from_secs(10)).await?;
+///# Ok(())
+///# }
+/// ```
+pub async fn wait_until<F, Fut, T>(mut condition: F, check_delay: Duration, timeout: Duration) -> Result<T, CmdError>
+    where F: FnMut() -> Fut,
+          Fut: Future<Output = Result<Option<T>, CmdError>>
+{
+    let mut interval = interval(check_delay);
+
+    let start = Instant::now();
+    loop {
+        if start.elapsed() > timeout {
+            return Err(CmdError::WaitTimeout);
+        }
+
+        if let Some(result) = condition().await? {
+            return Ok(result);
+        }
+
+        interval.tick().await;
+    }
 }
 
 #[async_trait]
-trait AppiumWaitOnSelector<T> where Self: Sized {
+trait AppiumWaitOnSelector<T> where Self: Sized + Sync {
     /// Checks if target can be located, then returns the result.
     /// If not found, waits for given delay and retries.
     /// Loops until a timeout is exceeded.
     async fn wait(self) -> Result<T, CmdError> {
         let wait = self.get_wait();
-        let mut interval = interval(wait.check_delay);
-        let timeout = wait.timeout;
-
-        let start = Instant::now();
-        loop {
-            if start.elapsed() > timeout {
-                return Err(CmdError::WaitTimeout);
-            }
-
-            {
-                let find_element = self.locate();
-                if let Some(result) = find_element.await? {
-                    return Ok(result);
-                }
-            }
-
-            interval.tick().await;
-        }
+        wait_until(|| self.locate(), wait.check_delay, wait.timeout).await
     }
 
     /// Returns wait parameters
-    fn get_wait(&self) -> &Wait;
+    fn get_wait(&self) -> &Wait<'_>;
 
     /// Logic for locating the target.
     async fn locate(&self) -> Result<Option<T>, CmdError>;
@@ -227,7 +345,7 @@ struct WaitOnMultiple<'a>(WaitSelector<'a>);
 
 #[async_trait]
 impl<'a> AppiumWaitOnSelector<Element> for WaitOnSingle<'a> {
-    fn get_wait(&self) -> &Wait {
+    fn get_wait(&self) -> &Wait<'_> {
         &self.0.wait
     }
 
@@ -238,7 +356,7 @@ impl<'a> AppiumWaitOnSelector<Element> for WaitOnSingle<'a> {
 
 #[async_trait]
 impl<'a> AppiumWaitOnSelector<Vec<Element>> for WaitOnMultiple<'a> {
-    fn get_wait(&self) -> &Wait {
+    fn get_wait(&self) -> &Wait<'_> {
         &self.0.wait
     }
 
@@ -261,4 +379,109 @@ async fn find_all_elements(wait: &Wait<'_>, selector: By) -> Result<Option<Vec<E
         Err(CmdError::NoSuchElement(_)) => Ok(None),
         Err(err) => Err(err),
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use fantoccini::elements::{Element, ElementRef};
+    use fantoccini::error::CmdError;
+    use http::StatusCode;
+    use serde_json::json;
+    use crate::find::By;
+    use crate::mock::{android_client, MockResponse, MockTransport};
+    use crate::wait::{AppiumWait, TextMatcher, wait_until};
+
+    #[tokio::test]
+    async fn wait_until_returns_when_condition_resolves() {
+        let polls = AtomicUsize::new(0);
+
+        let result = wait_until(|| async {
+            let poll = polls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(Some(poll).filter(|poll| *poll == 3))
+        }, Duration::from_millis(10), Duration::from_secs(5)).await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn wait_until_times_out() {
+        let result = wait_until(|| async {
+            Ok(None::<()>)
+        }, Duration::from_millis(10), Duration::from_millis(50)).await;
+
+        assert!(matches!(result, Err(CmdError::WaitTimeout)));
+    }
+
+    #[tokio::test]
+    async fn wait_until_returns_error_of_condition_at_once() {
+        let polls = AtomicUsize::new(0);
+
+        let result = wait_until(|| async {
+            polls.fetch_add(1, Ordering::SeqCst);
+            Err::<Option<()>, _>(CmdError::InvalidArgument("battery".to_string(), "unknown".to_string()))
+        }, Duration::from_millis(10), Duration::from_secs(5)).await;
+
+        assert!(matches!(result, Err(CmdError::InvalidArgument(..))));
+        assert_eq!(polls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn element_wait_searches_within_element() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::no_such_element());
+        transport.respond(MockResponse::element("item-1"));
+        let client = android_client(&transport).await;
+        let list = Element::from_element_id((*client).clone(), ElementRef::from("list-1".to_string()));
+
+        let item = list.appium_wait()
+            .check_every(Duration::from_millis(10))
+            .for_element(By::id("item"))
+            .await
+            .unwrap();
+
+        assert_eq!(item.element_id().as_ref(), "item-1");
+        assert_eq!(transport.requests_to("/element").len(), 2);
+        assert_eq!(transport.requests_to("/element/list-1/element").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn element_text_wait_returns_element_when_text_changes() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::element("status-1"));
+        transport.respond(MockResponse::value(json!("Loading")));
+        transport.respond(MockResponse::element("status-1"));
+        transport.respond(MockResponse::value(json!(" Done ")));
+        let client = android_client(&transport).await;
+
+        let status = client.appium_wait()
+            .check_every(Duration::from_millis(10))
+            .for_element_text(By::id("status"), TextMatcher::Equals("Done".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(status.element_id().as_ref(), "status-1");
+        assert_eq!(transport.requests_to("/element").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn element_text_wait_locates_stale_element_again() {
+        let transport = MockTransport::new();
+        transport.respond(MockResponse::element("status-1"));
+        transport.respond(MockResponse::error(StatusCode::NOT_FOUND, "stale element reference", "The element is no longer attached to the DOM"));
+        transport.respond(MockResponse::element("status-2"));
+        transport.respond(MockResponse::value(json!("Done")));
+        let client = android_client(&transport).await;
+
+        let status = client.appium_wait()
+            .check_every(Duration::from_millis(10))
+            .for_element_text(By::id("status"), TextMatcher::Contains("Done".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(status.element_id().as_ref(), "status-2");
+        assert_eq!(transport.requests_to("/element").len(), 2);
+    }
+}